@@ -1,16 +1,47 @@
+use hex_literal::hex;
+#[cfg(feature = "permissioned")]
+use node_template_runtime::ValidatorSetConfig;
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	opaque::SessionKeys, AccountId, AssetId, AssetsConfig, AuraConfig, Balance, BalancesConfig,
+	BlockNumber, ClaimsConfig, CouncilMembershipConfig, EthereumAddress, GenesisConfig,
+	GrandpaConfig, ImOnlineConfig, ImOnlineId, KittiesConfig, SessionConfig, Signature,
+	StakerStatus, StakingConfig, SystemConfig, TechnicalMembershipConfig, TemplateModuleConfig,
+	VestingConfig, DAYS, EXISTENTIAL_DEPOSIT, HOURS, MAX_AUTHORITIES, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{sr25519, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_runtime::{
+	traits::{IdentifyAccount, Verify},
+	Perbill,
+};
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// The identifier of the stable token created at genesis.
+const STABLE_ASSET_ID: AssetId = 1;
+
+/// An allocation, in `Claims`, of the Ethereum account of the well-known development private key
+/// `0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80`.
+const DEV_CLAIM: EthereumAddress =
+	EthereumAddress(hex!("f39fd6e51aad88f6f4ce6ab8827279cfffb92266"));
+
+/// The amount each initial validator bonds.
+const STASH: Balance = 1 << 56;
+
+/// The names and IDs of the development and local testnet chains. The runtime of the
+/// `permissioned` feature is not compatible with the staking one, so its chains have their own.
+#[cfg(not(feature = "permissioned"))]
+const DEVELOPMENT: (&str, &str) = ("Development", "dev");
+#[cfg(not(feature = "permissioned"))]
+const LOCAL_TESTNET: (&str, &str) = ("Local Testnet", "local_testnet");
+#[cfg(feature = "permissioned")]
+const DEVELOPMENT: (&str, &str) = ("Permissioned Development", "permissioned_dev");
+#[cfg(feature = "permissioned")]
+const LOCAL_TESTNET: (&str, &str) = ("Permissioned Local Testnet", "permissioned_local_testnet");
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
 
@@ -31,9 +62,38 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Vest three quarters of the balances of the stash accounts among `seeds` linearly over a day,
+/// after a cliff of an hour.
+fn stash_vesting(seeds: &[&str]) -> Vec<(AccountId, BlockNumber, BlockNumber, Balance)> {
+	seeds
+		.iter()
+		.map(|seed| {
+			let stash = get_account_id_from_seed::<sr25519::Public>(&format!("{}//stash", seed));
+			(stash, HOURS, DAYS, 1 << 58)
+		})
+		.collect()
+}
+
+/// Allocate `DEV_CLAIM` the equivalent of an endowment, half of it vesting linearly over a day
+/// after a cliff of an hour.
+fn dev_claims() -> Vec<(EthereumAddress, Balance, Option<(Balance, Balance, BlockNumber)>)> {
+	let locked: Balance = 1 << 59;
+	vec![(DEV_CLAIM, 1 << 60, Some((locked, locked / Balance::from(DAYS), HOURS)))]
+}
+
+/// Generate the stash and controller accounts and the session keys of a validator.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AccountId, AuraId, GrandpaId, ImOnlineId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(&format!("{}//stash", s)),
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+		get_from_seed::<ImOnlineId>(s),
+	)
+}
+
+fn session_keys(aura: AuraId, grandpa: GrandpaId, im_online: ImOnlineId) -> SessionKeys {
+	SessionKeys { aura, grandpa, im_online }
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
@@ -41,17 +101,22 @@ pub fn development_config() -> Result<ChainSpec, String> {
 
 	Ok(ChainSpec::from_genesis(
 		// Name
-		"Development",
+		DEVELOPMENT.0,
 		// ID
-		"dev",
+		DEVELOPMENT.1,
 		ChainType::Development,
 		move || {
 			testnet_genesis(
 				wasm_binary,
-				// Initial PoA authorities
+				// Initial validators
 				vec![authority_keys_from_seed("Alice")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				// Council members
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				],
+				// Technical committee members
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -59,6 +124,19 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Vesting pre-funded accounts
+				stash_vesting(&["Alice", "Bob"]),
+				// Allocations claimable with Ethereum signatures
+				dev_claims(),
+				// Initial template value
+				Some(0),
+				// Initial per-account template values
+				vec![],
+				// Pre-minted kitties
+				vec![
+					(get_account_id_from_seed::<sr25519::Public>("Alice"), [1; 16]),
+					(get_account_id_from_seed::<sr25519::Public>("Bob"), [2; 16]),
+				],
 				true,
 			)
 		},
@@ -81,17 +159,26 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 
 	Ok(ChainSpec::from_genesis(
 		// Name
-		"Local Testnet",
+		LOCAL_TESTNET.0,
 		// ID
-		"local_testnet",
+		LOCAL_TESTNET.1,
 		ChainType::Local,
 		move || {
 			testnet_genesis(
 				wasm_binary,
-				// Initial PoA authorities
+				// Initial validators
 				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				// Council members
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				],
+				// Technical committee members
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Dave"),
+					get_account_id_from_seed::<sr25519::Public>("Eve"),
+				],
 				// Pre-funded accounts
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -107,6 +194,19 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Vesting pre-funded accounts
+				stash_vesting(&["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"]),
+				// Allocations claimable with Ethereum signatures
+				dev_claims(),
+				// Initial template value
+				Some(0),
+				// Initial per-account template values
+				vec![],
+				// Pre-minted kitties
+				vec![
+					(get_account_id_from_seed::<sr25519::Public>("Alice"), [1; 16]),
+					(get_account_id_from_seed::<sr25519::Public>("Bob"), [2; 16]),
+				],
 				true,
 			)
 		},
@@ -127,9 +227,15 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
-	root_key: AccountId,
+	initial_authorities: Vec<(AccountId, AccountId, AuraId, GrandpaId, ImOnlineId)>,
+	council_members: Vec<AccountId>,
+	technical_committee_members: Vec<AccountId>,
 	endowed_accounts: Vec<AccountId>,
+	initial_vesting: Vec<(AccountId, BlockNumber, BlockNumber, Balance)>,
+	initial_claims: Vec<(EthereumAddress, Balance, Option<(Balance, Balance, BlockNumber)>)>,
+	initial_something: Option<u64>,
+	initial_something_of: Vec<(AccountId, u32)>,
+	initial_kitties: Vec<(AccountId, [u8; 16])>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -141,16 +247,75 @@ fn testnet_genesis(
 			// Configure endowed accounts with initial balance of 1 << 60.
 			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
 		},
-		aura: AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+		// The authorities are set by `Session`, from the session keys of the elected validators.
+		aura: AuraConfig { authorities: vec![] },
+		grandpa: GrandpaConfig { authorities: vec![] },
+		im_online: ImOnlineConfig { keys: vec![] },
+		// With the `permissioned` feature, `ValidatorSet` rather than `Staking` chooses the
+		// validators.
+		#[cfg(feature = "permissioned")]
+		validator_set: ValidatorSetConfig {
+			initial_validators: initial_authorities.iter().map(|x| x.0.clone()).collect(),
 		},
-		grandpa: GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		session: SessionConfig {
+			keys: initial_authorities
+				.iter()
+				.map(|x| {
+					(x.0.clone(), x.0.clone(), session_keys(x.2.clone(), x.3.clone(), x.4.clone()))
+				})
+				.collect(),
 		},
-		sudo: SudoConfig {
-			// Assign network admin rights.
-			key: Some(root_key),
+		staking: StakingConfig {
+			// Up to `MAX_AUTHORITIES` validators are elected, so anyone may bond and validate.
+			validator_count: MAX_AUTHORITIES,
+			minimum_validator_count: 1,
+			// The initial validators are never slashed, so that development chains keep going.
+			invulnerables: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+			slash_reward_fraction: Perbill::from_percent(10),
+			stakers: initial_authorities
+				.iter()
+				.map(|x| (x.0.clone(), x.1.clone(), STASH, StakerStatus::Validator))
+				.collect(),
+			..Default::default()
 		},
 		transaction_payment: Default::default(),
+		// The council's members are set through `CouncilMembership`.
+		council: Default::default(),
+		council_membership: CouncilMembershipConfig {
+			members: council_members,
+			phantom: Default::default(),
+		},
+		// The technical committee's members are set through `TechnicalMembership`.
+		technical_committee: Default::default(),
+		technical_membership: TechnicalMembershipConfig {
+			members: technical_committee_members,
+			phantom: Default::default(),
+		},
+		democracy: Default::default(),
+		treasury: Default::default(),
+		template_module: TemplateModuleConfig {
+			something: initial_something,
+			something_of: initial_something_of,
+		},
+		kitties: KittiesConfig { kitties: initial_kitties },
+		assets: AssetsConfig {
+			// A sufficient stable token, owned by the first endowed account, in which fees are
+			// charged at a tenth of their amount in the native token.
+			assets: vec![(
+				STABLE_ASSET_ID,
+				endowed_accounts[0].clone(),
+				true,
+				EXISTENTIAL_DEPOSIT / 10,
+			)],
+			metadata: vec![(STABLE_ASSET_ID, b"Dev Dollar".to_vec(), b"DUSD".to_vec(), 6)],
+			accounts: endowed_accounts
+				.iter()
+				.map(|k| (STABLE_ASSET_ID, k.clone(), 1 << 50))
+				.collect(),
+		},
+		// Each vesting account's balance less its liquid part unlocks linearly over the given
+		// number of blocks, starting at the given block.
+		vesting: VestingConfig { vesting: initial_vesting },
+		claims: ClaimsConfig { claims: initial_claims },
 	}
 }
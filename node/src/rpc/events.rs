@@ -0,0 +1,599 @@
+//! A subscription to the runtime events, filtered by pallet, variant and account.
+//!
+//! The events of every block are read from `System::Events` and turned into JSON by walking the
+//! `scale-info` description of their type in the metadata of the runtime at that block, so that
+//! the events of blocks built by other versions of the runtime are decoded correctly too.
+
+use std::{
+	collections::HashMap,
+	iter,
+	sync::{Arc, Mutex},
+};
+
+use codec::{Compact, Decode};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType};
+use futures::{channel::oneshot, future, stream, FutureExt, StreamExt};
+use jsonrpsee::{proc_macros::rpc, PendingSubscription};
+use node_template_runtime::{opaque::Block, AccountId, Hash};
+use sc_client_api::{Backend, BlockchainEvents, StateBackend};
+use sc_rpc::SubscriptionTaskExecutor;
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sp_api::{Core, Metadata, ProvideRuntimeApi};
+use sp_core::{crypto::Ss58Codec, hashing::twox_128};
+use sp_runtime::generic::BlockId;
+
+/// Which events to push to a subscriber. Every field that is set must match.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilter {
+	/// The name of the pallet in `construct_runtime!`, e.g. `TemplateModule`.
+	pub pallet: Option<String>,
+	/// The name of the event, e.g. `SomethingStored`.
+	pub variant: Option<String>,
+	/// An account that must appear in the event's fields.
+	pub account: Option<AccountId>,
+	/// Follow finalized blocks instead of new best blocks.
+	#[serde(default)]
+	pub finalized: bool,
+}
+
+/// An event that matched a subscriber's filter.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchedEvent {
+	/// The block that emitted the event.
+	pub block_hash: Hash,
+	/// The index of the extrinsic that emitted the event, if any.
+	pub extrinsic_index: Option<u32>,
+	/// The name of the pallet that emitted the event.
+	pub pallet: String,
+	/// The name of the event.
+	pub variant: String,
+	/// The fields of the event: an object for named fields, an array for unnamed ones.
+	pub fields: Value,
+}
+
+/// An item pushed to a subscriber.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EventNotification {
+	/// An event that matched the subscriber's filter.
+	Event(MatchedEvent),
+	/// The events of a block could not be read, so some may have been missed.
+	Error(EventsError),
+}
+
+/// The reason the events of a block could not be read.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventsError {
+	/// The block whose events could not be read.
+	pub block_hash: Hash,
+	/// What went wrong.
+	pub message: String,
+}
+
+/// Runtime events RPC methods.
+#[rpc(client, server)]
+pub trait EventsApi {
+	/// Subscribe to the events of new best blocks, or finalized blocks, that match `filter`.
+	///
+	/// A block whose events cannot be read is reported with an error item instead.
+	#[subscription(
+		name = "events_subscribe" => "events_event",
+		unsubscribe = "events_unsubscribe",
+		item = EventNotification
+	)]
+	fn subscribe(&self, filter: EventFilter);
+}
+
+/// The event decoders of the runtime versions seen so far, by `spec_version`.
+type Decoders = Mutex<HashMap<u32, Arc<EventDecoder>>>;
+
+/// Provides the runtime events RPC methods.
+pub struct Events<C, B> {
+	client: Arc<C>,
+	backend: Arc<B>,
+	decoders: Arc<Decoders>,
+	executor: SubscriptionTaskExecutor,
+}
+
+impl<C, B> Events<C, B> {
+	/// Create a new `Events` RPC handler.
+	pub fn new(client: Arc<C>, backend: Arc<B>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, backend, decoders: Default::default(), executor }
+	}
+}
+
+impl<C, B> EventsApiServer for Events<C, B>
+where
+	C: BlockchainEvents<Block> + ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	C::Api: Core<Block> + Metadata<Block>,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	fn subscribe(&self, pending: PendingSubscription, filter: EventFilter) {
+		let blocks = if filter.finalized {
+			// A finality notification also finalizes the blocks on the route to it.
+			self.client
+				.finality_notification_stream()
+				.flat_map(|notification| {
+					let route = notification.tree_route.iter().copied();
+					stream::iter(route.chain(iter::once(notification.hash)).collect::<Vec<_>>())
+				})
+				.boxed()
+		} else {
+			self.client
+				.import_notification_stream()
+				.filter_map(|notification| {
+					future::ready(notification.is_new_best.then(|| notification.hash))
+				})
+				.boxed()
+		};
+
+		let client = self.client.clone();
+		let backend = self.backend.clone();
+		let decoders = self.decoders.clone();
+		let filter = Arc::new(filter);
+		let executor = self.executor.clone();
+		let events = blocks
+			.then(move |hash| {
+				let (client, backend) = (client.clone(), backend.clone());
+				let (decoders, filter) = (decoders.clone(), filter.clone());
+				let (tx, rx) = oneshot::channel();
+				// Runtime calls and state reads block, so they must not hold up the RPC tasks.
+				let read = async move {
+					let notifications =
+						notifications_at(&*client, &*backend, &decoders, hash, &filter);
+					let _ = tx.send(notifications);
+				};
+				executor.spawn_blocking("events-rpc-read", Some("rpc"), read.boxed());
+				rx.map(move |notifications| {
+					notifications.unwrap_or_else(|_| {
+						let message = "the events were not read".into();
+						vec![EventNotification::Error(EventsError { block_hash: hash, message })]
+					})
+				})
+			})
+			.flat_map(stream::iter)
+			.boxed();
+
+		let fut = async move {
+			if let Some(mut sink) = pending.accept() {
+				sink.pipe_from_stream(events).await;
+			}
+		};
+		self.executor.spawn("events-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+}
+
+/// The events of block `hash` that match `filter`, or the error that kept them from being read.
+fn notifications_at<C, B>(
+	client: &C,
+	backend: &B,
+	decoders: &Decoders,
+	hash: Hash,
+	filter: &EventFilter,
+) -> Vec<EventNotification>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: Core<Block> + Metadata<Block>,
+	B: Backend<Block>,
+{
+	decoder_at(client, decoders, hash)
+		.and_then(|decoder| matching_events(backend, &decoder, hash, filter))
+		.map(|events| events.into_iter().map(EventNotification::Event).collect())
+		.unwrap_or_else(|message| {
+			vec![EventNotification::Error(EventsError { block_hash: hash, message })]
+		})
+}
+
+/// The decoder for the events of block `hash`, built from the metadata of the runtime at that
+/// block the first time its version is seen.
+fn decoder_at<C>(client: &C, decoders: &Decoders, hash: Hash) -> Result<Arc<EventDecoder>, String>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: Core<Block> + Metadata<Block>,
+{
+	let at = BlockId::Hash(hash);
+	let api = client.runtime_api();
+	let spec_version = api.version(&at).map_err(|e| e.to_string())?.spec_version;
+	if let Some(decoder) = decoders.lock().expect("no panics while locked; qed").get(&spec_version)
+	{
+		return Ok(decoder.clone())
+	}
+
+	let metadata = api.metadata(&at).map_err(|e| e.to_string())?;
+	let metadata =
+		RuntimeMetadataPrefixed::decode(&mut &metadata[..]).map_err(|e| e.to_string())?;
+	let decoder = Arc::new(EventDecoder::new(metadata)?);
+	decoders
+		.lock()
+		.expect("no panics while locked; qed")
+		.insert(spec_version, decoder.clone());
+	Ok(decoder)
+}
+
+/// The events of block `hash` that match `filter`.
+fn matching_events<B: Backend<Block>>(
+	backend: &B,
+	decoder: &EventDecoder,
+	hash: Hash,
+	filter: &EventFilter,
+) -> Result<Vec<MatchedEvent>, String> {
+	let key = [twox_128(b"System"), twox_128(b"Events")].concat();
+	let state = backend.state_at(BlockId::Hash(hash)).map_err(|e| e.to_string())?;
+	let raw = match state.storage(&key).map_err(|e| format!("{:?}", e))? {
+		Some(raw) => raw,
+		None => return Ok(Vec::new()),
+	};
+
+	let events = decoder.decode_records(&raw).map_err(|e| e.to_string())?;
+	Ok(events
+		.into_iter()
+		.filter(|event| filter.matches(event))
+		.map(|event| MatchedEvent {
+			block_hash: hash,
+			extrinsic_index: event.extrinsic_index,
+			pallet: event.pallet,
+			variant: event.variant,
+			fields: event.fields,
+		})
+		.collect())
+}
+
+impl EventFilter {
+	fn matches(&self, event: &DecodedEvent) -> bool {
+		self.pallet.as_ref().map_or(true, |pallet| *pallet == event.pallet) &&
+			self.variant.as_ref().map_or(true, |variant| *variant == event.variant) &&
+			self.account.as_ref().map_or(true, |account| event.accounts.contains(account))
+	}
+}
+
+/// A runtime event, decoded into JSON.
+#[derive(Debug)]
+struct DecodedEvent {
+	/// The index of the extrinsic that emitted the event, if any.
+	extrinsic_index: Option<u32>,
+	pallet: String,
+	variant: String,
+	fields: Value,
+	/// Every account that appears in the fields.
+	accounts: Vec<AccountId>,
+}
+
+/// Decodes runtime events into JSON with the `scale-info` description of `System::Events` in a
+/// runtime's metadata.
+struct EventDecoder {
+	registry: PortableRegistry,
+	/// The type of the records in `System::Events`.
+	record_type: u32,
+}
+
+impl EventDecoder {
+	/// Create a decoder for the events of the runtime described by `metadata`.
+	fn new(metadata: RuntimeMetadataPrefixed) -> Result<Self, String> {
+		let metadata = match metadata.1 {
+			RuntimeMetadata::V14(metadata) => metadata,
+			_ => return Err("unsupported metadata version".into()),
+		};
+		let events = metadata
+			.pallets
+			.iter()
+			.find(|pallet| pallet.name == "System")
+			.and_then(|pallet| pallet.storage.as_ref())
+			.and_then(|storage| storage.entries.iter().find(|entry| entry.name == "Events"))
+			.ok_or("no `System::Events` in the metadata")?;
+		let events_type = match &events.ty {
+			StorageEntryType::Plain(ty) => ty.id(),
+			StorageEntryType::Map { .. } => return Err("`System::Events` is not a value".into()),
+		};
+		let record_type = match metadata.types.resolve(events_type).map(|ty| ty.type_def()) {
+			Some(TypeDef::Sequence(sequence)) => sequence.type_param().id(),
+			_ => return Err("`System::Events` is not a sequence".into()),
+		};
+
+		Ok(Self { registry: metadata.types, record_type })
+	}
+
+	/// Decode the SCALE-encoded value of `System::Events`.
+	fn decode_records(&self, mut input: &[u8]) -> Result<Vec<DecodedEvent>, codec::Error> {
+		let len = Compact::<u32>::decode(&mut input)?.0;
+		let events = (0..len)
+			.map(|_| self.decode_record(&mut input))
+			.collect::<Result<Vec<_>, _>>()?;
+		if !input.is_empty() {
+			return Err("trailing bytes after the event records".into())
+		}
+		Ok(events)
+	}
+
+	/// Decode an `EventRecord`, keeping its `phase` and `event` and skipping its other fields.
+	fn decode_record(&self, input: &mut &[u8]) -> Result<DecodedEvent, codec::Error> {
+		let ty = self.registry.resolve(self.record_type).ok_or("unknown type")?;
+		let fields = match ty.type_def() {
+			TypeDef::Composite(composite) => composite.fields(),
+			_ => return Err("expected an event record".into()),
+		};
+
+		let mut phase = None;
+		let mut event = None;
+		for field in fields {
+			match field.name().map(String::as_str) {
+				Some("phase") => phase = Some(frame_system::Phase::decode(input)?),
+				Some("event") => event = Some(self.decode_event(field.ty().id(), input)?),
+				_ => {
+					self.decode_value(field.ty().id(), input, &mut Vec::new())?;
+				},
+			}
+		}
+
+		let mut event = event.ok_or("event record without an event")?;
+		event.extrinsic_index = match phase.ok_or("event record without a phase")? {
+			frame_system::Phase::ApplyExtrinsic(index) => Some(index),
+			_ => None,
+		};
+		Ok(event)
+	}
+
+	/// Decode a runtime event of type `id`.
+	///
+	/// The runtime's `Event` has a variant per pallet, holding the pallet's own `Event`.
+	fn decode_event(&self, id: u32, input: &mut &[u8]) -> Result<DecodedEvent, codec::Error> {
+		let mut accounts = Vec::new();
+		let (pallet, fields) = self.decode_variant(id, input)?;
+		let pallet_event = fields.first().ok_or("pallet variant without an event")?;
+		let (variant, fields) = self.decode_variant(pallet_event.ty().id(), input)?;
+		let fields = self.decode_fields(fields, input, &mut accounts)?;
+
+		Ok(DecodedEvent { extrinsic_index: None, pallet, variant, fields, accounts })
+	}
+
+	/// Decode the index of a variant of enum `id`, returning its name and fields.
+	fn decode_variant(
+		&self,
+		id: u32,
+		input: &mut &[u8],
+	) -> Result<(String, &[Field<PortableForm>]), codec::Error> {
+		let ty = self.registry.resolve(id).ok_or("unknown type")?;
+		let variants = match ty.type_def() {
+			TypeDef::Variant(variants) => variants.variants(),
+			_ => return Err("expected an enum".into()),
+		};
+		let index = u8::decode(input)?;
+		let variant =
+			variants.iter().find(|v| v.index() == index).ok_or("unknown variant index")?;
+
+		Ok((variant.name().clone(), variant.fields()))
+	}
+
+	/// Decode `fields` into an object if they are named, or else into an array, unless there is
+	/// only one.
+	fn decode_fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+		accounts: &mut Vec<AccountId>,
+	) -> Result<Value, codec::Error> {
+		if fields.iter().all(|field| field.name().is_some()) && !fields.is_empty() {
+			let mut object = Map::new();
+			for field in fields {
+				let name = field.name().cloned().unwrap_or_default();
+				object.insert(name, self.decode_value(field.ty().id(), input, accounts)?);
+			}
+			return Ok(Value::Object(object))
+		}
+
+		let mut values = fields
+			.iter()
+			.map(|field| self.decode_value(field.ty().id(), input, accounts))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(match values.len() {
+			0 => Value::Null,
+			1 => values.remove(0),
+			_ => Value::Array(values),
+		})
+	}
+
+	/// Decode a value of type `id`. Accounts are rendered as SS58 addresses.
+	fn decode_value(
+		&self,
+		id: u32,
+		input: &mut &[u8],
+		accounts: &mut Vec<AccountId>,
+	) -> Result<Value, codec::Error> {
+		let ty = self.registry.resolve(id).ok_or("unknown type")?;
+		if ty.path().ident().as_deref() == Some("AccountId32") {
+			let account = AccountId::decode(input)?;
+			let address = account.to_ss58check();
+			accounts.push(account);
+			return Ok(Value::String(address))
+		}
+
+		Ok(match ty.type_def() {
+			TypeDef::Composite(composite) =>
+				self.decode_fields(composite.fields(), input, accounts)?,
+			TypeDef::Variant(_) => {
+				let (name, fields) = self.decode_variant(id, input)?;
+				if fields.is_empty() {
+					Value::String(name)
+				} else {
+					let mut object = Map::new();
+					object.insert(name, self.decode_fields(fields, input, accounts)?);
+					Value::Object(object)
+				}
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				let element = sequence.type_param().id();
+				Value::Array(
+					(0..len)
+						.map(|_| self.decode_value(element, input, accounts))
+						.collect::<Result<_, _>>()?,
+				)
+			},
+			TypeDef::Array(array) => {
+				let element = array.type_param().id();
+				Value::Array(
+					(0..array.len())
+						.map(|_| self.decode_value(element, input, accounts))
+						.collect::<Result<_, _>>()?,
+				)
+			},
+			TypeDef::Tuple(tuple) => Value::Array(
+				tuple
+					.fields()
+					.iter()
+					.map(|field| self.decode_value(field.id(), input, accounts))
+					.collect::<Result<_, _>>()?,
+			),
+			TypeDef::Primitive(primitive) => decode_primitive(primitive, input)?,
+			// Compact encodings of all sizes decode as a `u128`.
+			TypeDef::Compact(_) => number(Compact::<u128>::decode(input)?.0),
+			TypeDef::BitSequence(_) => return Err("bit sequences are not supported".into()),
+		})
+	}
+}
+
+fn decode_primitive(
+	primitive: &TypeDefPrimitive,
+	input: &mut &[u8],
+) -> Result<Value, codec::Error> {
+	Ok(match primitive {
+		TypeDefPrimitive::Bool => bool::decode(input)?.into(),
+		TypeDefPrimitive::Char =>
+			char::from_u32(u32::decode(input)?).ok_or("invalid char")?.to_string().into(),
+		TypeDefPrimitive::Str => String::decode(input)?.into(),
+		TypeDefPrimitive::U8 => u8::decode(input)?.into(),
+		TypeDefPrimitive::U16 => u16::decode(input)?.into(),
+		TypeDefPrimitive::U32 => u32::decode(input)?.into(),
+		TypeDefPrimitive::U64 => u64::decode(input)?.into(),
+		TypeDefPrimitive::U128 => number(u128::decode(input)?),
+		TypeDefPrimitive::I8 => i8::decode(input)?.into(),
+		TypeDefPrimitive::I16 => i16::decode(input)?.into(),
+		TypeDefPrimitive::I32 => i32::decode(input)?.into(),
+		TypeDefPrimitive::I64 => i64::decode(input)?.into(),
+		TypeDefPrimitive::I128 => i128::decode(input)?.to_string().into(),
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			sp_core::bytes::to_hex(&<[u8; 32]>::decode(input)?, false).into(),
+	})
+}
+
+/// A JSON number, or a string if it does not fit in a `u64`.
+fn number(n: u128) -> Value {
+	u64::try_from(n).map_or_else(|_| n.to_string().into(), Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use frame_system::{EventRecord, Phase};
+	use node_template_runtime::{pallet_template, Event, Runtime};
+	use serde_json::json;
+	use sp_keyring::AccountKeyring;
+
+	fn decoder() -> EventDecoder {
+		EventDecoder::new(Runtime::metadata()).unwrap()
+	}
+
+	fn record(phase: Phase, event: Event) -> EventRecord<Event, Hash> {
+		EventRecord { phase, event, topics: vec![Hash::repeat_byte(1)] }
+	}
+
+	fn records() -> Vec<u8> {
+		let alice = AccountKeyring::Alice.to_account_id();
+		vec![
+			record(
+				Phase::Initialization,
+				Event::System(frame_system::Event::NewAccount { account: alice.clone() }),
+			),
+			record(
+				Phase::ApplyExtrinsic(2),
+				Event::TemplateModule(pallet_template::Event::SomethingStored(alice, None, 42)),
+			),
+		]
+		.encode()
+	}
+
+	#[test]
+	fn events_are_decoded_with_the_runtime_metadata() {
+		let alice = AccountKeyring::Alice.to_account_id();
+		let events = decoder().decode_records(&records()).unwrap();
+		assert_eq!(events.len(), 2);
+
+		assert_eq!(events[0].extrinsic_index, None);
+		assert_eq!((&*events[0].pallet, &*events[0].variant), ("System", "NewAccount"));
+		assert_eq!(events[0].fields, json!({ "account": alice.to_ss58check() }));
+		assert_eq!(events[0].accounts, vec![alice.clone()]);
+
+		assert_eq!(events[1].extrinsic_index, Some(2));
+		assert_eq!(
+			(&*events[1].pallet, &*events[1].variant),
+			("TemplateModule", "SomethingStored")
+		);
+		assert_eq!(events[1].fields, json!([alice.to_ss58check(), "None", 42]));
+		assert_eq!(events[1].accounts, vec![alice]);
+	}
+
+	#[test]
+	fn filters_match_every_field_that_is_set() {
+		let events = decoder().decode_records(&records()).unwrap();
+		let matching = |filter: EventFilter| {
+			events
+				.iter()
+				.filter(|event| filter.matches(event))
+				.map(|event| event.variant.as_str())
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(matching(EventFilter::default()), vec!["NewAccount", "SomethingStored"]);
+		assert_eq!(
+			matching(EventFilter { pallet: Some("TemplateModule".into()), ..Default::default() }),
+			vec!["SomethingStored"]
+		);
+		assert_eq!(
+			matching(EventFilter {
+				pallet: Some("System".into()),
+				variant: Some("SomethingStored".into()),
+				..Default::default()
+			}),
+			Vec::<&str>::new()
+		);
+		assert_eq!(
+			matching(EventFilter {
+				account: Some(AccountKeyring::Alice.to_account_id()),
+				..Default::default()
+			}),
+			vec!["NewAccount", "SomethingStored"]
+		);
+		assert_eq!(
+			matching(EventFilter {
+				account: Some(AccountKeyring::Bob.to_account_id()),
+				..Default::default()
+			}),
+			Vec::<&str>::new()
+		);
+	}
+
+	#[test]
+	fn malformed_records_are_rejected() {
+		let decoder = decoder();
+		let raw = records();
+
+		assert!(decoder.decode_records(&raw[..raw.len() - 1]).is_err());
+		assert!(decoder.decode_records(&[&raw[..], &[0]].concat()).is_err());
+
+		// The byte after the length and the phase of the first record is its pallet index.
+		let mut unknown_pallet = raw;
+		unknown_pallet[2] = u8::MAX;
+		assert!(decoder.decode_records(&unknown_pallet).is_err());
+	}
+
+	#[test]
+	fn a_block_without_events_decodes_to_none() {
+		let raw = Vec::<EventRecord<Event, Hash>>::new().encode();
+		assert!(decoder().decode_records(&raw).unwrap().is_empty());
+	}
+}
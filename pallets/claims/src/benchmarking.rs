@@ -0,0 +1,52 @@
+//! Benchmarking setup for pallet-claims
+
+use super::*;
+
+use crate::{
+	secp_utils::{eth, sig},
+	Pallet as Claims,
+};
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{Currency, EnsureOrigin};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+/// An Ethereum key.
+fn secret() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&sp_io::hashing::keccak_256(b"claimant")).unwrap()
+}
+
+/// The worst case of a vesting schedule, which vests from the first block.
+fn vesting<T: Config>(value: BalanceOf<T>) -> Option<VestingOf<T>> {
+	Some((value, 1u32.into(), 1u32.into()))
+}
+
+benchmarks! {
+	claim {
+		let secret = secret();
+		let value = <CurrencyOf<T>>::minimum_balance() * 10u32.into();
+		Claims::<T>::mint_claim(
+			T::MintOrigin::successful_origin(),
+			eth(&secret),
+			value,
+			vesting::<T>(value),
+		)?;
+		let dest: T::AccountId = account("dest", 0, 0);
+		let signature = sig::<T>(&secret, &dest);
+	}: _(RawOrigin::None, dest.clone(), signature)
+	verify {
+		assert_eq!(Claims::<T>::claims(eth(&secret)), None);
+		assert_eq!(<CurrencyOf<T>>::free_balance(&dest), value);
+	}
+
+	mint_claim {
+		let address = eth(&secret());
+		let value = BalanceOf::<T>::max_value();
+		let origin = T::MintOrigin::successful_origin();
+	}: _<T::Origin>(origin, address, value, vesting::<T>(value))
+	verify {
+		assert_eq!(Claims::<T>::claims(address), Some(value));
+	}
+
+	impl_benchmark_test_suite!(Claims, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,327 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Claims Pallet
+//!
+//! Distributes pre-launch token allocations, which are recorded against Ethereum addresses. The
+//! holder of an allocation claims it to an account of their choice by signing that account with
+//! the key of the Ethereum address, as an Ethereum signed message prefixed by `Prefix`.
+//!
+//! Claims are unsigned transactions whose signature is checked in `ValidateUnsigned`, so claimants
+//! need no tokens to claim. The claimed tokens are minted into the account, and an allocation may
+//! come with a vesting schedule, which is added to the account along with them.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::WeightInfo;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, VestingSchedule},
+	};
+	use frame_system::pallet_prelude::*;
+	#[cfg(feature = "std")]
+	use serde::{Deserialize, Serialize};
+	use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+	use sp_runtime::traits::{CheckedSub, Saturating};
+	use sp_std::vec::Vec;
+
+	/// The balance type of the currency in which claims are paid out.
+	pub type BalanceOf<T> =
+		<CurrencyOf<T> as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The currency in which claims are paid out.
+	pub type CurrencyOf<T> = <<T as Config>::VestingSchedule as VestingSchedule<
+		<T as frame_system::Config>::AccountId,
+	>>::Currency;
+
+	/// A vesting schedule of a claim: the amount locked, the amount unlocked per block, and the
+	/// block from which it starts unlocking.
+	pub type VestingOf<T> = (BalanceOf<T>, BalanceOf<T>, <T as frame_system::Config>::BlockNumber);
+
+	/// The `InvalidTransaction::Custom` code of a claim whose signature is not a valid Ethereum
+	/// signature.
+	pub const INVALID_ETHEREUM_SIGNATURE: u8 = 0;
+
+	/// The `InvalidTransaction::Custom` code of a claim signed by an address without a claim.
+	pub const SIGNER_HAS_NO_CLAIM: u8 = 1;
+
+	/// An Ethereum address.
+	#[derive(
+		Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct EthereumAddress(pub [u8; 20]);
+
+	/// A recoverable ECDSA signature by an Ethereum key: `r`, `s` and the recovery ID `v`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct EcdsaSignature(pub [u8; 65]);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The vesting schedules of claims, and the currency in which claims are paid out.
+		type VestingSchedule: VestingSchedule<Self::AccountId, Moment = Self::BlockNumber>;
+		/// The prefix of the message that claimants sign, followed by the hex-encoded account.
+		#[pallet::constant]
+		type Prefix: Get<&'static [u8]>;
+		/// The origin that may add claims.
+		type MintOrigin: EnsureOrigin<Self::Origin>;
+		/// The priority of claims in the transaction pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	// The unclaimed allocation of each Ethereum address.
+	#[pallet::storage]
+	#[pallet::getter(fn claims)]
+	pub type Claims<T: Config> = StorageMap<_, Identity, EthereumAddress, BalanceOf<T>>;
+
+	// The total of all unclaimed allocations.
+	#[pallet::storage]
+	#[pallet::getter(fn total)]
+	pub type Total<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	// The vesting schedule of each unclaimed allocation that vests.
+	#[pallet::storage]
+	#[pallet::getter(fn vesting)]
+	pub type Vesting<T: Config> = StorageMap<_, Identity, EthereumAddress, VestingOf<T>>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The allocations by Ethereum address, and their vesting schedules.
+		pub claims: Vec<(EthereumAddress, BalanceOf<T>, Option<VestingOf<T>>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { claims: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (address, value, vesting) in &self.claims {
+				<Pallet<T>>::mint(*address, *value, *vesting);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An allocation was added. [address, amount]
+		Minted(EthereumAddress, BalanceOf<T>),
+		/// An allocation was claimed. [who, address, amount]
+		Claimed(T::AccountId, EthereumAddress, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The signature is not a valid Ethereum signature.
+		InvalidEthereumSignature,
+		/// The signing address has no claim.
+		SignerHasNoClaim,
+		/// The address already has a claim.
+		AlreadyHasClaim,
+		/// The total of the unclaimed allocations is less than the claim.
+		PotUnderflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claim the allocation of the Ethereum address that signed `dest` to the account `dest`.
+		///
+		/// The signature is over the Ethereum signed message of `Prefix` followed by the hex
+		/// encoding of `dest`, without a `0x` prefix.
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			ethereum_signature: EcdsaSignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let signer = Self::signer(&dest, &ethereum_signature)
+				.ok_or(Error::<T>::InvalidEthereumSignature)?;
+			Self::process_claim(signer, dest)
+		}
+
+		/// Add an allocation of `value` to `who`, vesting by `vesting_schedule` if any.
+		#[pallet::weight(T::WeightInfo::mint_claim())]
+		pub fn mint_claim(
+			origin: OriginFor<T>,
+			who: EthereumAddress,
+			value: BalanceOf<T>,
+			vesting_schedule: Option<VestingOf<T>>,
+		) -> DispatchResult {
+			T::MintOrigin::ensure_origin(origin)?;
+			ensure!(!<Claims<T>>::contains_key(&who), Error::<T>::AlreadyHasClaim);
+
+			Self::mint(who, value, vesting_schedule);
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Accept a `claim` transaction signed by an Ethereum address with a claim.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (dest, ethereum_signature) = match call {
+				Call::claim { dest, ethereum_signature } => (dest, ethereum_signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			let signer = Self::signer(dest, ethereum_signature)
+				.ok_or(InvalidTransaction::Custom(INVALID_ETHEREUM_SIGNATURE))?;
+			if !<Claims<T>>::contains_key(&signer) {
+				return InvalidTransaction::Custom(SIGNER_HAS_NO_CLAIM).into()
+			}
+
+			ValidTransaction::with_tag_prefix("Claims")
+				.priority(T::UnsignedPriority::get())
+				// Each allocation is claimed once.
+				.and_provides(signer)
+				.longevity(TransactionLongevity::max_value())
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The Ethereum signed message of `Prefix` followed by `what`.
+		pub(crate) fn ethereum_signable_message(what: &[u8]) -> Vec<u8> {
+			let prefix = T::Prefix::get();
+			let mut length = prefix.len() + what.len();
+			let mut digits = Vec::new();
+			while length > 0 {
+				digits.push(b'0' + (length % 10) as u8);
+				length /= 10;
+			}
+
+			let mut message = b"\x19Ethereum Signed Message:\n".to_vec();
+			message.extend(digits.into_iter().rev());
+			message.extend_from_slice(prefix);
+			message.extend_from_slice(what);
+			message
+		}
+
+		/// The Ethereum address whose key signed the claim of `dest` with `signature`, `None` if
+		/// the signature is invalid.
+		fn signer(dest: &T::AccountId, signature: &EcdsaSignature) -> Option<EthereumAddress> {
+			let message =
+				keccak_256(&Self::ethereum_signable_message(&dest.using_encoded(to_ascii_hex)));
+			let public = secp256k1_ecdsa_recover(&signature.0, &message).ok()?;
+			let mut address = EthereumAddress::default();
+			address.0.copy_from_slice(&keccak_256(&public[..])[12..]);
+			Some(address)
+		}
+
+		/// Add an allocation of `value` to `who`, vesting by `vesting` if any.
+		fn mint(who: EthereumAddress, value: BalanceOf<T>, vesting: Option<VestingOf<T>>) {
+			<Total<T>>::mutate(|total| *total = total.saturating_add(value));
+			<Claims<T>>::insert(&who, value);
+			if let Some(vesting) = vesting {
+				<Vesting<T>>::insert(&who, vesting);
+			}
+
+			Self::deposit_event(Event::Minted(who, value));
+		}
+
+		/// Pay the allocation of `signer` out to `dest`, along with its vesting schedule.
+		fn process_claim(signer: EthereumAddress, dest: T::AccountId) -> DispatchResult {
+			let value = <Claims<T>>::get(&signer).ok_or(Error::<T>::SignerHasNoClaim)?;
+			let total = Self::total().checked_sub(&value).ok_or(Error::<T>::PotUnderflow)?;
+			let vesting = <Vesting<T>>::get(&signer);
+			if let Some((locked, per_block, starting_block)) = vesting {
+				T::VestingSchedule::can_add_vesting_schedule(
+					&dest,
+					locked,
+					per_block,
+					starting_block,
+				)?;
+			}
+
+			CurrencyOf::<T>::deposit_creating(&dest, value);
+			if let Some((locked, per_block, starting_block)) = vesting {
+				T::VestingSchedule::add_vesting_schedule(&dest, locked, per_block, starting_block)
+					.expect("The schedule was checked to be addable above; qed");
+			}
+
+			<Total<T>>::put(total);
+			<Claims<T>>::remove(&signer);
+			<Vesting<T>>::remove(&signer);
+
+			Self::deposit_event(Event::Claimed(dest, signer, value));
+			Ok(())
+		}
+	}
+
+	/// The lowercase hex encoding of `data`.
+	pub(crate) fn to_ascii_hex(data: &[u8]) -> Vec<u8> {
+		let mut hex = Vec::with_capacity(data.len() * 2);
+		let mut push_nibble =
+			|nibble| hex.push(if nibble < 10 { b'0' + nibble } else { b'a' - 10 + nibble });
+		for byte in data {
+			push_nibble(byte / 16);
+			push_nibble(byte % 16);
+		}
+		hex
+	}
+}
+
+/// Helpers to make Ethereum keys and sign claims with them.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub mod secp_utils {
+	use super::*;
+	use sp_io::hashing::keccak_256;
+
+	/// The public key of `secret`.
+	pub fn public(secret: &libsecp256k1::SecretKey) -> libsecp256k1::PublicKey {
+		libsecp256k1::PublicKey::from_secret_key(secret)
+	}
+
+	/// The Ethereum address of `secret`.
+	pub fn eth(secret: &libsecp256k1::SecretKey) -> EthereumAddress {
+		let mut address = EthereumAddress::default();
+		address.0.copy_from_slice(&keccak_256(&public(secret).serialize()[1..65])[12..]);
+		address
+	}
+
+	/// The signature by `secret` of a claim to `dest`.
+	pub fn sig<T: Config>(secret: &libsecp256k1::SecretKey, dest: &T::AccountId) -> EcdsaSignature {
+		use codec::Encode;
+
+		let message = keccak_256(&<Pallet<T>>::ethereum_signable_message(
+			&dest.using_encoded(pallet::to_ascii_hex),
+		));
+		let (signature, recovery_id) =
+			libsecp256k1::sign(&libsecp256k1::Message::parse(&message), secret);
+		let mut bytes = [0u8; 65];
+		bytes[0..64].copy_from_slice(&signature.serialize()[..]);
+		bytes[64] = recovery_id.serialize();
+		EcdsaSignature(bytes)
+	}
+}
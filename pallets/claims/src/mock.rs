@@ -0,0 +1,117 @@
+use crate as pallet_claims;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use frame_system::{self as system, EnsureRoot};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
+	BuildStorage,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Vesting: pallet_vesting::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Claims: pallet_claims::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// The maximum number of vesting schedules of an account.
+pub const MAX_VESTING_SCHEDULES: u32 = 1;
+
+impl pallet_vesting::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = ConstU64<1>;
+	type WeightInfo = ();
+	const MAX_VESTING_SCHEDULES: u32 = MAX_VESTING_SCHEDULES;
+}
+
+parameter_types! {
+	pub Prefix: &'static [u8] = b"Pay RUSTs to the TEST account:";
+}
+
+impl pallet_claims::Config for Test {
+	type Event = Event;
+	type VestingSchedule = Vesting;
+	type Prefix = Prefix;
+	type MintOrigin = EnsureRoot<u64>;
+	type UnsignedPriority = ConstU64<100>;
+	type WeightInfo = ();
+}
+
+/// The balance of the accounts endowed at genesis.
+pub const ENDOWMENT: u64 = 100;
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with(Default::default())
+}
+
+// Build genesis storage with the given claims pallet genesis config. Accounts 1 to 3 are endowed.
+pub fn new_test_ext_with(claims: pallet_claims::GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig { balances: (1..=3).map(|who| (who, ENDOWMENT)).collect() },
+		vesting: Default::default(),
+		claims,
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	// Events are not deposited in block 0.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
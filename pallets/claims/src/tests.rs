@@ -0,0 +1,195 @@
+use crate::{
+	mock::*,
+	secp_utils::{eth, sig},
+	EcdsaSignature, Error, EthereumAddress, Event as ClaimsEvent, GenesisConfig,
+	INVALID_ETHEREUM_SIGNATURE, SIGNER_HAS_NO_CLAIM,
+};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, traits::VestingSchedule, unsigned::ValidateUnsigned};
+use pallet_vesting::Error as VestingError;
+use sp_runtime::{
+	transaction_validity::{
+		InvalidTransaction, TransactionLongevity, TransactionSource, ValidTransaction,
+	},
+	DispatchError,
+};
+
+/// The Ethereum key with a plain allocation at genesis.
+fn alice() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&sp_io::hashing::keccak_256(b"Alice")).unwrap()
+}
+
+/// The Ethereum key with a vesting allocation at genesis.
+fn bob() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&sp_io::hashing::keccak_256(b"Bob")).unwrap()
+}
+
+/// An Ethereum key without an allocation.
+fn eve() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&sp_io::hashing::keccak_256(b"Eve")).unwrap()
+}
+
+/// The vesting schedule of Bob's allocation.
+const BOB_VESTING: (u64, u64, u64) = (50, 5, 10);
+
+// Build genesis storage in which Alice may claim 100 and Bob 200, of which 50 vests.
+fn new_test_ext_with_claims() -> sp_io::TestExternalities {
+	new_test_ext_with(GenesisConfig {
+		claims: vec![(eth(&alice()), 100, None), (eth(&bob()), 200, Some(BOB_VESTING))],
+	})
+}
+
+#[test]
+fn genesis_records_the_allocations() {
+	new_test_ext_with_claims().execute_with(|| {
+		assert_eq!(Claims::total(), 300);
+		assert_eq!(Claims::claims(eth(&alice())), Some(100));
+		assert_eq!(Claims::vesting(eth(&alice())), None);
+		assert_eq!(Claims::claims(eth(&bob())), Some(200));
+		assert_eq!(Claims::vesting(eth(&bob())), Some(BOB_VESTING));
+	});
+}
+
+#[test]
+fn claim_pays_the_allocation_to_the_destination() {
+	new_test_ext_with_claims().execute_with(|| {
+		assert_ok!(Claims::claim(Origin::none(), 4, sig::<Test>(&alice(), &4)));
+
+		assert_eq!(Balances::free_balance(4), 100);
+		assert_eq!(Vesting::vesting_balance(&4), None);
+		assert_eq!(Claims::claims(eth(&alice())), None);
+		assert_eq!(Claims::total(), 200);
+		System::assert_last_event(ClaimsEvent::Claimed(4, eth(&alice()), 100).into());
+	});
+}
+
+#[test]
+fn claim_adds_the_vesting_schedule() {
+	new_test_ext_with_claims().execute_with(|| {
+		assert_ok!(Claims::claim(Origin::none(), 1, sig::<Test>(&bob(), &1)));
+
+		assert_eq!(Balances::free_balance(1), ENDOWMENT + 200);
+		assert_eq!(Vesting::vesting_balance(&1), Some(BOB_VESTING.0));
+		assert_eq!(Claims::vesting(eth(&bob())), None);
+		// The locked part of the allocation cannot be spent before it vests.
+		assert!(Balances::transfer(Origin::signed(1), 2, ENDOWMENT + 200).is_err());
+		assert_ok!(Balances::transfer(Origin::signed(1), 2, ENDOWMENT + 200 - BOB_VESTING.0));
+	});
+}
+
+#[test]
+fn claims_are_claimed_once() {
+	new_test_ext_with_claims().execute_with(|| {
+		assert_ok!(Claims::claim(Origin::none(), 4, sig::<Test>(&alice(), &4)));
+
+		assert_noop!(
+			Claims::claim(Origin::none(), 5, sig::<Test>(&alice(), &5)),
+			Error::<Test>::SignerHasNoClaim
+		);
+	});
+}
+
+#[test]
+fn claim_needs_the_signature_of_an_address_with_a_claim() {
+	new_test_ext_with_claims().execute_with(|| {
+		assert_noop!(
+			Claims::claim(Origin::none(), 4, sig::<Test>(&eve(), &4)),
+			Error::<Test>::SignerHasNoClaim
+		);
+		// A signature of another destination recovers another address.
+		assert_noop!(
+			Claims::claim(Origin::none(), 4, sig::<Test>(&alice(), &5)),
+			Error::<Test>::SignerHasNoClaim
+		);
+		assert_noop!(
+			Claims::claim(Origin::none(), 4, EcdsaSignature([0; 65])),
+			Error::<Test>::InvalidEthereumSignature
+		);
+	});
+}
+
+#[test]
+fn claim_is_unsigned() {
+	new_test_ext_with_claims().execute_with(|| {
+		assert_noop!(
+			Claims::claim(Origin::signed(1), 4, sig::<Test>(&alice(), &4)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn claim_fails_when_the_vesting_schedule_cannot_be_added() {
+	new_test_ext_with_claims().execute_with(|| {
+		for _ in 0..MAX_VESTING_SCHEDULES {
+			assert_ok!(Vesting::add_vesting_schedule(&1, 10, 1, 10));
+		}
+
+		assert_noop!(
+			Claims::claim(Origin::none(), 1, sig::<Test>(&bob(), &1)),
+			VestingError::<Test>::AtMaxVestingSchedules
+		);
+	});
+}
+
+#[test]
+fn mint_claim_adds_an_allocation() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Claims::mint_claim(Origin::signed(1), eth(&eve()), 50, None),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Claims::mint_claim(Origin::root(), eth(&eve()), 50, Some(BOB_VESTING)));
+		assert_eq!(Claims::total(), 50);
+		System::assert_last_event(ClaimsEvent::Minted(eth(&eve()), 50).into());
+		assert_noop!(
+			Claims::mint_claim(Origin::root(), eth(&eve()), 10, None),
+			Error::<Test>::AlreadyHasClaim
+		);
+
+		assert_ok!(Claims::claim(Origin::none(), 4, sig::<Test>(&eve(), &4)));
+		assert_eq!(Balances::free_balance(4), 50);
+		assert_eq!(Vesting::vesting_balance(&4), Some(BOB_VESTING.0));
+	});
+}
+
+#[test]
+fn validate_unsigned_accepts_only_claims_of_addresses_with_a_claim() {
+	new_test_ext_with_claims().execute_with(|| {
+		let claim = |who: &libsecp256k1::SecretKey, dest: u64| {
+			let call = crate::Call::claim { dest, ethereum_signature: sig::<Test>(who, &dest) };
+			Claims::validate_unsigned(TransactionSource::External, &call)
+		};
+
+		assert_eq!(
+			claim(&alice(), 4),
+			Ok(ValidTransaction {
+				priority: 100,
+				requires: vec![],
+				provides: vec![("Claims", eth(&alice())).encode()],
+				longevity: TransactionLongevity::max_value(),
+				propagate: true,
+			})
+		);
+		assert_eq!(claim(&eve(), 4), InvalidTransaction::Custom(SIGNER_HAS_NO_CLAIM).into());
+		assert_eq!(
+			Claims::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::claim { dest: 4, ethereum_signature: EcdsaSignature([0; 65]) },
+			),
+			InvalidTransaction::Custom(INVALID_ETHEREUM_SIGNATURE).into()
+		);
+		assert_eq!(
+			Claims::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::mint_claim {
+					who: EthereumAddress::default(),
+					value: 10,
+					vesting_schedule: None,
+				},
+			),
+			InvalidTransaction::Call.into()
+		);
+	});
+}
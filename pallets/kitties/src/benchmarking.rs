@@ -0,0 +1,82 @@
+//! Benchmarking setup for pallet-kitties
+
+use super::*;
+
+use crate::Pallet as Kitties;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup};
+
+/// An account with enough funds to buy kitties and hold their deposits.
+fn funded<T: Config>(who: T::AccountId) -> T::AccountId {
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// Fund `owner`, mint `n` kitties for it and return the DNA of the last one.
+fn mint<T: Config>(owner: &T::AccountId, n: u32) -> Result<Dna, &'static str> {
+	funded::<T>(owner.clone());
+	for _ in 0..n {
+		Kitties::<T>::create_kitty(RawOrigin::Signed(owner.clone()).into())?;
+	}
+	Kitties::<T>::kitties_owned(owner).last().copied().ok_or("no kitty was minted")
+}
+
+benchmarks! {
+	create_kitty {
+		let caller: T::AccountId = whitelisted_caller();
+		let owned = T::MaxKittiesOwned::get() - 1;
+		mint::<T>(&caller, owned)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Kitties::<T>::kitties_owned(&caller).len() as u32, owned + 1);
+	}
+
+	transfer {
+		// The kitty is the first one the caller owns, and the recipient has room for one more.
+		let caller: T::AccountId = whitelisted_caller();
+		let kitty = mint::<T>(&caller, 1)?;
+		mint::<T>(&caller, T::MaxKittiesOwned::get() - 1)?;
+		let to: T::AccountId = account("to", 0, 0);
+		mint::<T>(&to, T::MaxKittiesOwned::get() - 1)?;
+		let to_lookup = T::Lookup::unlookup(to.clone());
+	}: _(RawOrigin::Signed(caller), to_lookup, kitty)
+	verify {
+		assert_eq!(Kitties::<T>::kitties(kitty).map(|kitty| kitty.owner), Some(to));
+	}
+
+	breed_kitty {
+		let caller: T::AccountId = whitelisted_caller();
+		let owned = T::MaxKittiesOwned::get() - 1;
+		let mom = mint::<T>(&caller, 1)?;
+		let dad = mint::<T>(&caller, owned - 1)?;
+	}: _(RawOrigin::Signed(caller.clone()), mom, dad)
+	verify {
+		assert_eq!(Kitties::<T>::kitties_owned(&caller).len() as u32, owned + 1);
+	}
+
+	set_price {
+		let caller: T::AccountId = whitelisted_caller();
+		let kitty = mint::<T>(&caller, 1)?;
+		let price = BalanceOf::<T>::max_value();
+	}: _(RawOrigin::Signed(caller), kitty, Some(price))
+	verify {
+		assert_eq!(Kitties::<T>::kitties(kitty).and_then(|kitty| kitty.price), Some(price));
+	}
+
+	buy_kitty {
+		let seller = funded::<T>(account("seller", 0, 0));
+		let kitty = mint::<T>(&seller, 1)?;
+		mint::<T>(&seller, T::MaxKittiesOwned::get() - 1)?;
+		let price = T::Currency::minimum_balance() * 10u32.into();
+		Kitties::<T>::set_price(RawOrigin::Signed(seller.clone()).into(), kitty, Some(price))?;
+		let caller = funded::<T>(whitelisted_caller());
+		mint::<T>(&caller, T::MaxKittiesOwned::get() - 1)?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty, price)
+	verify {
+		assert_eq!(Kitties::<T>::kitties(kitty).map(|kitty| kitty.owner), Some(caller));
+	}
+
+	impl_benchmark_test_suite!(Kitties, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,348 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Kitties Pallet
+//!
+//! Collectible kitties, each identified by a unique DNA drawn from the runtime's randomness.
+//! Accounts may mint kitties up to `MaxKittiesOwned`, transfer them, breed two of their kitties
+//! into a new one, and put them up for sale. A kitty for sale is bought with `buy_kitty`, which
+//! moves the price from the buyer to the seller and the kitty from the seller to the buyer in one
+//! step.
+//!
+//! Minting or breeding a kitty reserves `KittyDeposit` from its owner for the storage it takes up.
+//! The deposit moves with the kitty: a transfer or a sale repatriates it from the reserved balance
+//! of the old owner to the reserved balance of the new one.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
+/// The log target of this pallet.
+const LOG_TARGET: &str = "runtime::kitties";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::WeightInfo;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_io::hashing::blake2_128;
+	use sp_runtime::traits::{StaticLookup, Zero};
+	use sp_std::vec::Vec;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	/// The balance type of the currency in which kitties are sold and deposits are held.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The DNA of a kitty, which also identifies it.
+	pub type Dna = [u8; 16];
+
+	/// The gender of a kitty, derived from its DNA.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum Gender {
+		/// A kitty whose DNA starts with an even byte.
+		Male,
+		/// A kitty whose DNA starts with an odd byte.
+		Female,
+	}
+
+	impl From<&Dna> for Gender {
+		fn from(dna: &Dna) -> Self {
+			if dna[0] % 2 == 0 {
+				Gender::Male
+			} else {
+				Gender::Female
+			}
+		}
+	}
+
+	/// A kitty, as recorded in `Kitties`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Kitty<AccountId, Balance> {
+		/// The DNA of the kitty.
+		pub dna: Dna,
+		/// The price the kitty is for sale at, `None` if it is not for sale.
+		pub price: Option<Balance>,
+		/// The gender of the kitty.
+		pub gender: Gender,
+		/// The account that owns the kitty.
+		pub owner: AccountId,
+		/// The deposit reserved from the owner for as long as it owns the kitty.
+		pub deposit: Balance,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The currency in which kitties are sold and deposits are held.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The deposit reserved from the owner of a kitty minted by `create_kitty` or
+		/// `breed_kitty`.
+		#[pallet::constant]
+		type KittyDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of kitties an account may own.
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+		/// The source of randomness the DNA of new kitties is drawn from.
+		type KittyRandomness: Randomness<Self::Hash, Self::BlockNumber>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	// Every kitty by its DNA. A counted map keeps track of the number of kitties.
+	#[pallet::storage]
+	#[pallet::getter(fn kitties)]
+	pub type Kitties<T: Config> =
+		CountedStorageMap<_, Twox64Concat, Dna, Kitty<T::AccountId, BalanceOf<T>>>;
+
+	// The DNA of the kitties owned by each account. Accounts that own no kitty have no entry.
+	#[pallet::storage]
+	#[pallet::getter(fn kitties_owned)]
+	pub type KittiesOwned<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<Dna, T::MaxKittiesOwned>,
+		ValueQuery,
+	>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The kitties minted at genesis, by owner and DNA. They hold no deposit.
+		pub kitties: Vec<(T::AccountId, Dna)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { kitties: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (owner, dna) in &self.kitties {
+				<Pallet<T>>::mint(owner, *dna, Zero::zero())
+					.expect("The genesis kitties must be mintable");
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A kitty was minted or bred. [owner, kitty]
+		Created(T::AccountId, Dna),
+		/// A kitty was transferred. [from, to, kitty]
+		Transferred(T::AccountId, T::AccountId, Dna),
+		/// The owner of a kitty put it up for sale, or took it off sale with a price of `None`.
+		/// [kitty, price]
+		PriceSet(Dna, Option<BalanceOf<T>>),
+		/// A kitty was sold. [seller, buyer, kitty, price]
+		Sold(T::AccountId, T::AccountId, Dna, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account would own more than `MaxKittiesOwned` kitties.
+		TooManyOwned,
+		/// A kitty with the same DNA already exists.
+		DuplicateKitty,
+		/// The kitty does not exist.
+		NoKitty,
+		/// The kitty is owned by another account.
+		NotOwner,
+		/// A kitty cannot be transferred to or bought by its owner.
+		TransferToSelf,
+		/// A kitty cannot be bred with itself.
+		SameParents,
+		/// The kitty is not for sale.
+		NotForSale,
+		/// The kitty's price is above the buyer's limit.
+		BidPriceTooLow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Mint a new kitty with a random DNA, owned by the caller, and reserve `KittyDeposit`
+		/// from the caller.
+		#[pallet::weight(T::WeightInfo::create_kitty())]
+		pub fn create_kitty(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let dna = Self::random_dna(&who);
+			Self::mint(&who, dna, T::KittyDeposit::get())
+		}
+
+		/// Transfer a kitty owned by the caller to `to`, taking it off sale.
+		///
+		/// The kitty's deposit moves to the reserved balance of `to`.
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+			kitty: Dna,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+
+			let details = <Kitties<T>>::get(&kitty).ok_or(Error::<T>::NoKitty)?;
+			ensure!(details.owner == who, Error::<T>::NotOwner);
+			Self::do_transfer(details, to)
+		}
+
+		/// Breed two kitties owned by the caller into a new kitty, whose DNA mixes theirs, and
+		/// reserve `KittyDeposit` from the caller.
+		#[pallet::weight(T::WeightInfo::breed_kitty())]
+		pub fn breed_kitty(origin: OriginFor<T>, parent_1: Dna, parent_2: Dna) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(parent_1 != parent_2, Error::<T>::SameParents);
+			for parent in [&parent_1, &parent_2] {
+				let details = <Kitties<T>>::get(parent).ok_or(Error::<T>::NoKitty)?;
+				ensure!(details.owner == who, Error::<T>::NotOwner);
+			}
+
+			let dna = Self::breed_dna(&who, &parent_1, &parent_2);
+			Self::mint(&who, dna, T::KittyDeposit::get())
+		}
+
+		/// Put a kitty owned by the caller up for sale at `new_price`, or take it off sale with
+		/// `None`.
+		#[pallet::weight(T::WeightInfo::set_price())]
+		pub fn set_price(
+			origin: OriginFor<T>,
+			kitty: Dna,
+			new_price: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut details = <Kitties<T>>::get(&kitty).ok_or(Error::<T>::NoKitty)?;
+			ensure!(details.owner == who, Error::<T>::NotOwner);
+			details.price = new_price;
+			<Kitties<T>>::insert(&kitty, details);
+
+			Self::deposit_event(Event::PriceSet(kitty, new_price));
+			Ok(())
+		}
+
+		/// Buy a kitty that is for sale at no more than `limit_price`.
+		///
+		/// The price is transferred from the caller to the seller and the kitty, with its deposit,
+		/// from the seller to the caller. If either fails, neither happens.
+		#[pallet::weight(T::WeightInfo::buy_kitty())]
+		pub fn buy_kitty(
+			origin: OriginFor<T>,
+			kitty: Dna,
+			limit_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+
+			let details = <Kitties<T>>::get(&kitty).ok_or(Error::<T>::NoKitty)?;
+			let price = details.price.ok_or(Error::<T>::NotForSale)?;
+			ensure!(price <= limit_price, Error::<T>::BidPriceTooLow);
+			let seller = details.owner.clone();
+			ensure!(seller != buyer, Error::<T>::TransferToSelf);
+
+			T::Currency::transfer(&buyer, &seller, price, ExistenceRequirement::KeepAlive)?;
+			Self::do_transfer(details, buyer.clone())?;
+
+			Self::deposit_event(Event::Sold(seller, buyer, kitty, price));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// A random DNA for a new kitty of `owner`.
+		fn random_dna(owner: &T::AccountId) -> Dna {
+			let (random, _) = T::KittyRandomness::random(&b"dna"[..]);
+			// The randomness only changes from block to block, so mix in the owner and the number
+			// of kitties, which changes with every kitty created.
+			(random, owner, <Kitties<T>>::count()).using_encoded(blake2_128)
+		}
+
+		/// The DNA of a kitty of `owner` bred from `parent_1` and `parent_2`, taking each bit from
+		/// either parent at random.
+		fn breed_dna(owner: &T::AccountId, parent_1: &Dna, parent_2: &Dna) -> Dna {
+			let selector = Self::random_dna(owner);
+			let mut dna = Dna::default();
+			for (i, gene) in dna.iter_mut().enumerate() {
+				*gene = (!selector[i] & parent_1[i]) | (selector[i] & parent_2[i]);
+			}
+			dna
+		}
+
+		/// Create a kitty with `dna`, owned by `owner` and not for sale, and reserve `deposit` from
+		/// `owner` for it.
+		fn mint(owner: &T::AccountId, dna: Dna, deposit: BalanceOf<T>) -> DispatchResult {
+			ensure!(!<Kitties<T>>::contains_key(&dna), Error::<T>::DuplicateKitty);
+			<KittiesOwned<T>>::try_append(owner, dna).map_err(|_| Error::<T>::TooManyOwned)?;
+			T::Currency::reserve(owner, deposit)?;
+
+			let kitty = Kitty {
+				dna,
+				price: None,
+				gender: Gender::from(&dna),
+				owner: owner.clone(),
+				deposit,
+			};
+			<Kitties<T>>::insert(&dna, kitty);
+
+			Self::deposit_event(Event::Created(owner.clone(), dna));
+			Ok(())
+		}
+
+		/// Transfer `kitty` and its deposit from its owner to `to`, taking it off sale.
+		fn do_transfer(
+			mut kitty: Kitty<T::AccountId, BalanceOf<T>>,
+			to: T::AccountId,
+		) -> DispatchResult {
+			let from = kitty.owner;
+			ensure!(from != to, Error::<T>::TransferToSelf);
+
+			<KittiesOwned<T>>::try_append(&to, kitty.dna).map_err(|_| Error::<T>::TooManyOwned)?;
+			let mut owned = <KittiesOwned<T>>::take(&from);
+			owned.retain(|dna| dna != &kitty.dna);
+			if !owned.is_empty() {
+				<KittiesOwned<T>>::insert(&from, owned);
+			}
+
+			// Only the part of the deposit that is still reserved can be moved, if some of it
+			// was slashed.
+			let unmoved = T::Currency::repatriate_reserved(
+				&from,
+				&to,
+				kitty.deposit,
+				BalanceStatus::Reserved,
+			)?;
+			kitty.deposit -= unmoved;
+
+			kitty.owner = to.clone();
+			kitty.price = None;
+			<Kitties<T>>::insert(&kitty.dna, &kitty);
+
+			Self::deposit_event(Event::Transferred(from, to, kitty.dna));
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,111 @@
+//! Storage migrations for the kitties pallet.
+//!
+//! Every change to the layout of the pallet's storage bumps `STORAGE_VERSION` in `lib.rs` and adds
+//! a module here with an `OnRuntimeUpgrade` implementation that brings the storage of a live chain
+//! up to date. The runtime registers the migrations it needs in its `Migrations` tuple.
+
+use super::*;
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_runtime::traits::Zero;
+use sp_std::marker::PhantomData;
+
+pub mod v1 {
+	use super::*;
+	use codec::Decode;
+
+	/// A kitty as recorded in `Kitties` up to version 0, before it held a deposit.
+	#[derive(Decode)]
+	struct OldKitty<AccountId, Balance> {
+		dna: Dna,
+		price: Option<Balance>,
+		gender: Gender,
+		owner: AccountId,
+	}
+
+	/// Migrates the pallet's storage from version 0 to version 1.
+	///
+	/// Version 1 reserves a deposit from the owner of every kitty minted, and records it with the
+	/// kitty. The kitties minted before hold no deposit, so they are recorded with a deposit of
+	/// zero. Version 1 also removes the entries of `KittiesOwned` that are left empty when an
+	/// account transfers its last kitty.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+			if on_chain_version == 0 {
+				let mut kitties = 0u64;
+				Kitties::<T>::translate::<OldKitty<T::AccountId, BalanceOf<T>>, _>(|_, old| {
+					kitties += 1;
+					Some(Kitty {
+						dna: old.dna,
+						price: old.price,
+						gender: old.gender,
+						owner: old.owner,
+						deposit: Zero::zero(),
+					})
+				});
+				let mut owners = 0u64;
+				KittiesOwned::<T>::translate::<BoundedVec<Dna, T::MaxKittiesOwned>, _>(
+					|_, owned| {
+						owners += 1;
+						(!owned.is_empty()).then(|| owned)
+					},
+				);
+				StorageVersion::new(1).put::<Pallet<T>>();
+				log::info!(
+					target: LOG_TARGET,
+					"migrated storage to version 1, recorded {} kitties without a deposit",
+					kitties
+				);
+				T::DbWeight::get().reads_writes(kitties + owners + 1, kitties + owners + 1)
+			} else {
+				log::warn!(
+					target: LOG_TARGET,
+					"skipping migration to version 1, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 0,
+				"the storage version must be 0 before migrating to version 1"
+			);
+			Self::set_temp_storage(Kitties::<T>::count(), "kitties");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"the storage version must be 1 after the migration"
+			);
+			let kitties: u32 = Self::get_temp_storage("kitties")
+				.ok_or("the number of kitties was not recorded before the migration")?;
+			frame_support::ensure!(
+				Kitties::<T>::iter_values().count() as u32 == kitties &&
+					Kitties::<T>::count() == kitties,
+				"the migration must keep every kitty"
+			);
+			frame_support::ensure!(
+				KittiesOwned::<T>::iter_values().all(|owned| !owned.is_empty()),
+				"the migration must remove the empty entries of `KittiesOwned`"
+			);
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,105 @@
+use crate as pallet_kitties;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
+		Kitties: pallet_kitties::{Pallet, Call, Config<T>, Storage, Event<T>},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+/// The balance of the accounts endowed at genesis.
+pub const ENDOWMENT: u64 = 100;
+
+/// The maximum number of kitties an account may own.
+pub const MAX_KITTIES_OWNED: u32 = 3;
+
+/// The deposit reserved for a minted kitty.
+pub const KITTY_DEPOSIT: u64 = 5;
+
+impl pallet_kitties::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type KittyDeposit = ConstU64<KITTY_DEPOSIT>;
+	type MaxKittiesOwned = ConstU32<MAX_KITTIES_OWNED>;
+	type KittyRandomness = RandomnessCollectiveFlip;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with(Default::default())
+}
+
+// Build genesis storage with the given kitties pallet genesis config. Accounts 1 to 3 are endowed.
+pub fn new_test_ext_with(kitties: pallet_kitties::GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig { balances: (1..=3).map(|who| (who, ENDOWMENT)).collect() },
+		kitties,
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	// Events are not deposited in block 0.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,244 @@
+use crate::{
+	migrations, mock::*, Dna, Error, Event as KittiesEvent, Gender, GenesisConfig,
+	Kitties as KittiesStorage, KittiesOwned, Kitty,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	BoundedVec,
+};
+use pallet_balances::Error as BalancesError;
+
+/// The DNA of the kitties `who` owns.
+fn owned(who: u64) -> Vec<Dna> {
+	Kitties::kitties_owned(who).into_inner()
+}
+
+/// Mint a kitty for `who` and return its DNA.
+fn create(who: u64) -> Dna {
+	assert_ok!(Kitties::create_kitty(Origin::signed(who)));
+	*owned(who).last().unwrap()
+}
+
+#[test]
+fn create_kitty_mints_a_kitty_for_the_caller() {
+	new_test_ext().execute_with(|| {
+		let dna = create(1);
+
+		assert_eq!(
+			Kitties::kitties(dna),
+			Some(Kitty {
+				dna,
+				price: None,
+				gender: Gender::from(&dna),
+				owner: 1,
+				deposit: KITTY_DEPOSIT
+			})
+		);
+		assert_eq!(owned(1), vec![dna]);
+		assert_eq!(Balances::reserved_balance(1), KITTY_DEPOSIT);
+		System::assert_last_event(KittiesEvent::Created(1, dna).into());
+	});
+}
+
+#[test]
+fn minting_fails_without_funds_for_the_deposit() {
+	new_test_ext().execute_with(|| {
+		// Account 4 is not endowed at genesis.
+		assert_noop!(
+			Kitties::create_kitty(Origin::signed(4)),
+			BalancesError::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn kitties_get_distinct_dna() {
+	new_test_ext().execute_with(|| {
+		let (first, second, third) = (create(1), create(1), create(2));
+
+		assert_ne!(first, second);
+		assert_ne!(second, third);
+		assert_ne!(first, third);
+	});
+}
+
+#[test]
+fn accounts_own_at_most_max_kitties() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MAX_KITTIES_OWNED {
+			create(1);
+		}
+		assert_noop!(Kitties::create_kitty(Origin::signed(1)), Error::<Test>::TooManyOwned);
+
+		let dna = create(2);
+		assert_noop!(Kitties::transfer(Origin::signed(2), 1, dna), Error::<Test>::TooManyOwned);
+	});
+}
+
+#[test]
+fn transfer_moves_ownership_and_takes_the_kitty_off_sale() {
+	new_test_ext().execute_with(|| {
+		let (dna, other) = (create(1), create(1));
+		assert_ok!(Kitties::set_price(Origin::signed(1), dna, Some(10)));
+
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, dna));
+		let kitty = Kitties::kitties(dna).unwrap();
+		assert_eq!(kitty.owner, 2);
+		assert_eq!(kitty.price, None);
+		assert_eq!(owned(1), vec![other]);
+		assert_eq!(owned(2), vec![dna]);
+		System::assert_last_event(KittiesEvent::Transferred(1, 2, dna).into());
+	});
+}
+
+#[test]
+fn the_deposit_moves_with_the_kitty() {
+	new_test_ext().execute_with(|| {
+		let dna = create(1);
+
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, dna));
+		assert_eq!(Kitties::kitties(dna).unwrap().deposit, KITTY_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT - KITTY_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(2), KITTY_DEPOSIT);
+		assert_eq!(Balances::free_balance(2), ENDOWMENT);
+
+		// An account that owns no kitty has no entry in `KittiesOwned`.
+		assert!(!KittiesOwned::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn only_the_owner_can_transfer_or_price_a_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Kitties::transfer(Origin::signed(1), 2, [0; 16]), Error::<Test>::NoKitty);
+		assert_noop!(
+			Kitties::set_price(Origin::signed(1), [0; 16], Some(10)),
+			Error::<Test>::NoKitty
+		);
+
+		let dna = create(1);
+		assert_noop!(Kitties::transfer(Origin::signed(2), 3, dna), Error::<Test>::NotOwner);
+		assert_noop!(Kitties::set_price(Origin::signed(2), dna, Some(10)), Error::<Test>::NotOwner);
+		assert_noop!(Kitties::transfer(Origin::signed(1), 1, dna), Error::<Test>::TransferToSelf);
+	});
+}
+
+#[test]
+fn breeding_mixes_the_dna_of_the_parents() {
+	new_test_ext().execute_with(|| {
+		let (mom, dad) = (create(1), create(1));
+
+		assert_ok!(Kitties::breed_kitty(Origin::signed(1), mom, dad));
+		let child = *owned(1).last().unwrap();
+		assert_eq!(owned(1).len(), 3);
+		assert_eq!(Balances::reserved_balance(1), 3 * KITTY_DEPOSIT);
+		System::assert_last_event(KittiesEvent::Created(1, child).into());
+
+		// Every bit of the child comes from one of its parents.
+		for ((child, mom), dad) in child.iter().zip(mom).zip(dad) {
+			assert_eq!(child & !(mom | dad), 0);
+			assert_eq!(!child & mom & dad, 0);
+		}
+	});
+}
+
+#[test]
+fn breeding_requires_two_kitties_of_the_caller() {
+	new_test_ext().execute_with(|| {
+		let (mom, dad) = (create(1), create(2));
+
+		assert_noop!(Kitties::breed_kitty(Origin::signed(1), mom, mom), Error::<Test>::SameParents);
+		assert_noop!(Kitties::breed_kitty(Origin::signed(1), mom, dad), Error::<Test>::NotOwner);
+		assert_noop!(Kitties::breed_kitty(Origin::signed(1), mom, [0; 16]), Error::<Test>::NoKitty);
+	});
+}
+
+#[test]
+fn buy_kitty_moves_funds_and_ownership() {
+	new_test_ext().execute_with(|| {
+		let dna = create(1);
+		assert_noop!(Kitties::buy_kitty(Origin::signed(2), dna, 10), Error::<Test>::NotForSale);
+
+		assert_ok!(Kitties::set_price(Origin::signed(1), dna, Some(10)));
+		System::assert_last_event(KittiesEvent::PriceSet(dna, Some(10)).into());
+		assert_noop!(Kitties::buy_kitty(Origin::signed(2), dna, 9), Error::<Test>::BidPriceTooLow);
+		assert_noop!(Kitties::buy_kitty(Origin::signed(1), dna, 10), Error::<Test>::TransferToSelf);
+
+		assert_ok!(Kitties::buy_kitty(Origin::signed(2), dna, 15));
+		System::assert_last_event(KittiesEvent::Sold(1, 2, dna, 10).into());
+		assert_eq!(Kitties::kitties(dna).unwrap().owner, 2);
+		assert_eq!(Kitties::kitties(dna).unwrap().price, None);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT - KITTY_DEPOSIT + 10);
+		assert_eq!(Balances::free_balance(2), ENDOWMENT - 10);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), KITTY_DEPOSIT);
+	});
+}
+
+#[test]
+fn buy_kitty_changes_nothing_if_either_side_fails() {
+	new_test_ext().execute_with(|| {
+		let dna = create(1);
+		assert_ok!(Kitties::set_price(Origin::signed(1), dna, Some(ENDOWMENT)));
+
+		// The buyer cannot pay the price and stay alive.
+		assert_noop!(
+			Kitties::buy_kitty(Origin::signed(2), dna, ENDOWMENT),
+			BalancesError::<Test>::KeepAlive
+		);
+
+		// The buyer can pay, but owns too many kitties.
+		assert_ok!(Kitties::set_price(Origin::signed(1), dna, Some(10)));
+		for _ in 0..MAX_KITTIES_OWNED {
+			create(2);
+		}
+		assert_noop!(Kitties::buy_kitty(Origin::signed(2), dna, 10), Error::<Test>::TooManyOwned);
+	});
+}
+
+#[test]
+fn genesis_config_mints_kitties() {
+	let (alice, bob) = ([1; 16], [2; 16]);
+	new_test_ext_with(GenesisConfig { kitties: vec![(1, alice), (2, bob)] }).execute_with(|| {
+		assert_eq!(owned(1), vec![alice]);
+		assert_eq!(owned(2), vec![bob]);
+		assert_eq!(Kitties::kitties(bob).unwrap().gender, Gender::Male);
+		assert_eq!(Kitties::kitties(alice).unwrap().gender, Gender::Female);
+		assert_eq!(Kitties::kitties(alice).unwrap().deposit, 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+#[should_panic(expected = "The genesis kitties must be mintable")]
+fn genesis_config_rejects_duplicate_kitties() {
+	new_test_ext_with(GenesisConfig { kitties: vec![(1, [1; 16]), (2, [1; 16])] });
+}
+
+#[test]
+fn migration_to_v1_records_kitties_without_a_deposit() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Kitties>();
+		let dna = [1; 16];
+		// A kitty in the layout of version 0, and an account left without kitties.
+		let old = (dna, Option::<u64>::None, Gender::Female, 1u64);
+		unhashed::put_raw(&KittiesStorage::<Test>::hashed_key_for(dna), &old.encode());
+		KittiesStorage::<Test>::initialize_counter();
+		KittiesOwned::<Test>::insert(1, BoundedVec::try_from(vec![dna]).unwrap());
+		KittiesOwned::<Test>::insert(2, BoundedVec::default());
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Kitties::on_chain_storage_version(), Kitties::current_storage_version());
+		assert_eq!(
+			Kitties::kitties(dna),
+			Some(Kitty { dna, price: None, gender: Gender::Female, owner: 1, deposit: 0 })
+		);
+		assert_eq!(owned(1), vec![dna]);
+		assert!(!KittiesOwned::<Test>::contains_key(2));
+	});
+}
@@ -0,0 +1,115 @@
+
+//! Estimated weights for pallet_kitties
+//!
+//! These weights are hand-written estimates, not benchmark results: the storage accesses follow
+//! each call's implementation, and the execution times are scaled from comparable FRAME
+//! pallets. Replace them with `./scripts/benchmark.sh pallet_kitties` on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+	fn create_kitty() -> Weight;
+	fn transfer() -> Weight;
+	fn breed_kitty() -> Weight;
+	fn set_price() -> Weight;
+	fn buy_kitty() -> Weight;
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Kitties CounterForKitties (r:1 w:1)
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn create_kitty() -> Weight {
+		(45_120_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	fn transfer() -> Weight {
+		(44_935_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Kitties Kitties (r:3 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Kitties CounterForKitties (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn breed_kitty() -> Weight {
+		(51_760_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	fn set_price() -> Weight {
+		(18_714_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Kitties KittiesOwned (r:2 w:2)
+	fn buy_kitty() -> Weight {
+		(66_482_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Kitties CounterForKitties (r:1 w:1)
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn create_kitty() -> Weight {
+		(45_120_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	fn transfer() -> Weight {
+		(44_935_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Kitties Kitties (r:3 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Kitties CounterForKitties (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn breed_kitty() -> Weight {
+		(51_760_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	fn set_price() -> Weight {
+		(18_714_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Kitties KittiesOwned (r:2 w:2)
+	fn buy_kitty() -> Weight {
+		(66_482_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+}
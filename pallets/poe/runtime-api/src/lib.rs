@@ -0,0 +1,35 @@
+//! Runtime API definition for the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A claim of a document hash.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ClaimInfo<AccountId, BlockNumber, Balance> {
+	/// The account that owns the claim.
+	pub owner: AccountId,
+	/// The block in which the hash was claimed.
+	pub block: BlockNumber,
+	/// The deposit held from the owner for the claim.
+	pub deposit: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to look up the claims of the proof of existence pallet.
+	pub trait PoeApi<AccountId, BlockNumber, Balance> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// Get the claim of `hash`, `None` if it is not claimed.
+		fn claim(hash: Vec<u8>) -> Option<ClaimInfo<AccountId, BlockNumber, Balance>>;
+	}
+}
@@ -0,0 +1,56 @@
+//! Benchmarking setup for pallet-poe
+
+use super::*;
+
+use crate::Pallet as Poe;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_std::{vec, vec::Vec};
+
+/// An account with enough funds to hold claim deposits.
+fn funded<T: Config>(who: T::AccountId) -> T::AccountId {
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// A hash of length `l` owned by `owner`.
+fn claimed<T: Config>(owner: &T::AccountId, l: u32) -> Result<Vec<u8>, &'static str> {
+	let hash = vec![1; l as usize];
+	Poe::<T>::create_claim(RawOrigin::Signed(owner.clone()).into(), hash.clone())?;
+	Ok(hash)
+}
+
+benchmarks! {
+	create_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let caller = funded::<T>(whitelisted_caller());
+		let hash = vec![1; l as usize];
+	}: _(RawOrigin::Signed(caller.clone()), hash.clone())
+	verify {
+		assert_eq!(Poe::<T>::claim_of(hash).map(|claim| claim.owner), Some(caller));
+	}
+
+	revoke_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let caller = funded::<T>(whitelisted_caller());
+		let hash = claimed::<T>(&caller, l)?;
+	}: _(RawOrigin::Signed(caller), hash.clone())
+	verify {
+		assert!(Poe::<T>::claim_of(hash).is_none());
+	}
+
+	transfer_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let caller = funded::<T>(whitelisted_caller());
+		let hash = claimed::<T>(&caller, l)?;
+		let dest = funded::<T>(account("dest", 0, 0));
+		let dest_lookup = T::Lookup::unlookup(dest.clone());
+	}: _(RawOrigin::Signed(caller), hash.clone(), dest_lookup)
+	verify {
+		assert_eq!(Poe::<T>::claim_of(hash).map(|claim| claim.owner), Some(dest));
+	}
+
+	impl_benchmark_test_suite!(Poe, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,190 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Proof of Existence Pallet
+//!
+//! Lets accounts claim the hash of a document, proving that the document existed when the claim
+//! was made. A claim records its owner and the block in which it was made, and holds a deposit
+//! from its owner for as long as it exists. The owner may revoke a claim or transfer it to
+//! another account, along with its deposit.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::WeightInfo;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{BalanceStatus, Currency, ReservableCurrency},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::vec::Vec;
+
+	/// The balance type of the currency in which claim deposits are held.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// A document hash, at most `MaxClaimLength` bytes long.
+	pub type ClaimOf<T> = BoundedVec<u8, <T as Config>::MaxClaimLength>;
+
+	/// A claim of a document hash, as recorded in `Claims`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Claim<AccountId, BlockNumber, Balance> {
+		/// The account that owns the claim.
+		pub owner: AccountId,
+		/// The block in which the hash was claimed.
+		pub block: BlockNumber,
+		/// The deposit held from the owner for the claim.
+		pub deposit: Balance,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The currency in which claim deposits are held.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The deposit held from the owner of each claim.
+		#[pallet::constant]
+		type ClaimDeposit: Get<BalanceOf<Self>>;
+		/// The maximum length of a claimed hash in bytes.
+		#[pallet::constant]
+		type MaxClaimLength: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	// The claim of each claimed hash.
+	#[pallet::storage]
+	#[pallet::getter(fn claims)]
+	pub type Claims<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ClaimOf<T>,
+		Claim<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account claimed a hash. [who, claim]
+		ClaimCreated(T::AccountId, ClaimOf<T>),
+		/// The owner of a claim revoked it. [who, claim]
+		ClaimRevoked(T::AccountId, ClaimOf<T>),
+		/// The owner of a claim transferred it to another account. [from, to, claim]
+		ClaimTransferred(T::AccountId, T::AccountId, ClaimOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The hash is longer than `MaxClaimLength`.
+		ClaimTooLong,
+		/// The hash has already been claimed.
+		AlreadyClaimed,
+		/// The hash has not been claimed.
+		NoSuchClaim,
+		/// The claim is owned by another account.
+		NotClaimOwner,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claim the hash of a document, holding `ClaimDeposit` from the caller.
+		#[pallet::weight(T::WeightInfo::create_claim(claim.len() as u32))]
+		pub fn create_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let claim = Self::bounded(claim)?;
+			ensure!(!<Claims<T>>::contains_key(&claim), Error::<T>::AlreadyClaimed);
+
+			let deposit = T::ClaimDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			let block = <frame_system::Pallet<T>>::block_number();
+			<Claims<T>>::insert(&claim, Claim { owner: who.clone(), block, deposit });
+
+			Self::deposit_event(Event::ClaimCreated(who, claim));
+			Ok(())
+		}
+
+		/// Revoke a claim owned by the caller, returning its deposit.
+		#[pallet::weight(T::WeightInfo::revoke_claim(claim.len() as u32))]
+		pub fn revoke_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let claim = Self::bounded(claim)?;
+			let Claim { deposit, .. } = Self::owned_by(&claim, &who)?;
+
+			<Claims<T>>::remove(&claim);
+			T::Currency::unreserve(&who, deposit);
+
+			Self::deposit_event(Event::ClaimRevoked(who, claim));
+			Ok(())
+		}
+
+		/// Transfer a claim owned by the caller to `dest`, along with its deposit.
+		///
+		/// The block in which the hash was claimed is kept.
+		#[pallet::weight(T::WeightInfo::transfer_claim(claim.len() as u32))]
+		pub fn transfer_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let claim = Self::bounded(claim)?;
+			let mut details = Self::owned_by(&claim, &who)?;
+
+			if dest != who {
+				T::Currency::repatriate_reserved(
+					&who,
+					&dest,
+					details.deposit,
+					BalanceStatus::Reserved,
+				)?;
+				details.owner = dest.clone();
+				<Claims<T>>::insert(&claim, details);
+			}
+
+			Self::deposit_event(Event::ClaimTransferred(who, dest, claim));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Look up the claim of `hash`, `None` if it is not claimed or too long to be claimed.
+		pub fn claim_of(
+			hash: Vec<u8>,
+		) -> Option<Claim<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+			<Claims<T>>::get(ClaimOf::<T>::try_from(hash).ok()?)
+		}
+
+		/// Bound `claim` by `MaxClaimLength`.
+		fn bounded(claim: Vec<u8>) -> Result<ClaimOf<T>, Error<T>> {
+			claim.try_into().map_err(|_| Error::<T>::ClaimTooLong)
+		}
+
+		/// The claim of `claim`, failing unless it is owned by `who`.
+		fn owned_by(
+			claim: &ClaimOf<T>,
+			who: &T::AccountId,
+		) -> Result<Claim<T::AccountId, T::BlockNumber, BalanceOf<T>>, Error<T>> {
+			let details = <Claims<T>>::get(claim).ok_or(Error::<T>::NoSuchClaim)?;
+			ensure!(&details.owner == who, Error::<T>::NotClaimOwner);
+			Ok(details)
+		}
+	}
+}
@@ -0,0 +1,137 @@
+use crate::{mock::*, Claim, ClaimOf, Error, Event as PoeEvent};
+use frame_support::{assert_noop, assert_ok};
+use pallet_balances::Error as BalancesError;
+
+fn hash(byte: u8) -> Vec<u8> {
+	vec![byte; MAX_CLAIM_LENGTH as usize]
+}
+
+fn bounded(claim: Vec<u8>) -> ClaimOf<Test> {
+	claim.try_into().unwrap()
+}
+
+#[test]
+fn create_claim_records_owner_and_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(Poe::create_claim(Origin::signed(1), hash(1)));
+
+		assert_eq!(
+			Poe::claims(bounded(hash(1))),
+			Some(Claim { owner: 1, block: 5, deposit: DEPOSIT })
+		);
+		assert_eq!(Poe::claim_of(hash(1)), Poe::claims(bounded(hash(1))));
+		System::assert_last_event(PoeEvent::ClaimCreated(1, bounded(hash(1))).into());
+	});
+}
+
+#[test]
+fn create_claim_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), hash(1)));
+		assert_ok!(Poe::create_claim(Origin::signed(1), hash(2)));
+
+		assert_eq!(Balances::reserved_balance(1), 2 * DEPOSIT);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT - 2 * DEPOSIT);
+	});
+}
+
+#[test]
+fn create_claim_fails_without_funds_for_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Poe::create_claim(Origin::signed(4), hash(1)),
+			BalancesError::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn hashes_can_only_be_claimed_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), hash(1)));
+		assert_noop!(Poe::create_claim(Origin::signed(1), hash(1)), Error::<Test>::AlreadyClaimed);
+		assert_noop!(Poe::create_claim(Origin::signed(2), hash(1)), Error::<Test>::AlreadyClaimed);
+	});
+}
+
+#[test]
+fn hashes_longer_than_the_maximum_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let too_long = vec![1; MAX_CLAIM_LENGTH as usize + 1];
+		assert_noop!(
+			Poe::create_claim(Origin::signed(1), too_long.clone()),
+			Error::<Test>::ClaimTooLong
+		);
+		assert_noop!(Poe::revoke_claim(Origin::signed(1), too_long), Error::<Test>::ClaimTooLong);
+	});
+}
+
+#[test]
+fn revoke_claim_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), hash(1)));
+		assert_ok!(Poe::revoke_claim(Origin::signed(1), hash(1)));
+
+		assert_eq!(Poe::claims(bounded(hash(1))), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT);
+		System::assert_last_event(PoeEvent::ClaimRevoked(1, bounded(hash(1))).into());
+
+		// The hash can be claimed again.
+		assert_ok!(Poe::create_claim(Origin::signed(2), hash(1)));
+	});
+}
+
+#[test]
+fn only_the_owner_can_revoke_or_transfer_a_claim() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Poe::revoke_claim(Origin::signed(1), hash(1)), Error::<Test>::NoSuchClaim);
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(1), hash(1), 2),
+			Error::<Test>::NoSuchClaim
+		);
+
+		assert_ok!(Poe::create_claim(Origin::signed(1), hash(1)));
+		assert_noop!(Poe::revoke_claim(Origin::signed(2), hash(1)), Error::<Test>::NotClaimOwner);
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(2), hash(1), 3),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_moves_ownership_and_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(Poe::create_claim(Origin::signed(1), hash(1)));
+
+		System::set_block_number(6);
+		assert_ok!(Poe::transfer_claim(Origin::signed(1), hash(1), 2));
+		assert_eq!(
+			Poe::claims(bounded(hash(1))),
+			Some(Claim { owner: 2, block: 5, deposit: DEPOSIT })
+		);
+		System::assert_last_event(PoeEvent::ClaimTransferred(1, 2, bounded(hash(1))).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT - DEPOSIT);
+		assert_eq!(Balances::reserved_balance(2), DEPOSIT);
+
+		// The new owner gets the deposit back when revoking the claim.
+		assert_noop!(Poe::revoke_claim(Origin::signed(1), hash(1)), Error::<Test>::NotClaimOwner);
+		assert_ok!(Poe::revoke_claim(Origin::signed(2), hash(1)));
+		assert_eq!(Balances::free_balance(2), ENDOWMENT + DEPOSIT);
+	});
+}
+
+#[test]
+fn claims_cannot_be_transferred_to_accounts_that_do_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Poe::create_claim(Origin::signed(1), hash(1)));
+		assert_noop!(
+			Poe::transfer_claim(Origin::signed(1), hash(1), 4),
+			BalancesError::<Test>::DeadAccount
+		);
+	});
+}
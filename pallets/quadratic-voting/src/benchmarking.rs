@@ -0,0 +1,76 @@
+//! Benchmarking setup for pallet-quadratic-voting
+
+use super::*;
+
+use crate::Pallet as QuadraticVoting;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get, Hooks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Saturating};
+use sp_std::boxed::Box;
+
+/// An account with enough funds to propose and vote.
+fn funded<T: Config>(who: T::AccountId) -> T::AccountId {
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// Make a proposal from `proposer` and return its index.
+fn propose<T: Config>(proposer: &T::AccountId) -> Result<ProposalIndex, &'static str> {
+	let call = T::BenchmarkProposal::get();
+	QuadraticVoting::<T>::propose(RawOrigin::Signed(proposer.clone()).into(), Box::new(call))?;
+	Ok(QuadraticVoting::<T>::proposal_count() - 1)
+}
+
+benchmarks! {
+	propose {
+		// The other open proposals end in different blocks, each of which has room for more.
+		let proposer = funded::<T>(account("proposer", 0, 0));
+		for i in 1 .. T::MaxProposals::get() {
+			frame_system::Pallet::<T>::set_block_number(i.into());
+			propose::<T>(&proposer)?;
+		}
+		frame_system::Pallet::<T>::set_block_number(T::MaxProposals::get().into());
+		let caller = funded::<T>(whitelisted_caller());
+		let call = T::BenchmarkProposal::get();
+	}: _(RawOrigin::Signed(caller), Box::new(call))
+	verify {
+		assert_eq!(Proposals::<T>::count(), T::MaxProposals::get());
+	}
+
+	vote {
+		let index = propose::<T>(&funded::<T>(account("proposer", 0, 0)))?;
+		let caller = funded::<T>(whitelisted_caller());
+	}: _(RawOrigin::Signed(caller.clone()), index, true, 10)
+	verify {
+		assert!(Voting::<T>::contains_key(index, &caller));
+	}
+
+	on_initialize {
+		// The proposals are rejected, the weight of the calls of passed proposals is added on top.
+		let n in 0 .. T::MaxEndingPerBlock::get();
+		let proposer = funded::<T>(account("proposer", 0, 0));
+		for _ in 0 .. n {
+			propose::<T>(&proposer)?;
+		}
+		let end = frame_system::Pallet::<T>::block_number().saturating_add(T::VotingPeriod::get());
+		frame_system::Pallet::<T>::set_block_number(end);
+	}: {
+		QuadraticVoting::<T>::on_initialize(end);
+	}
+	verify {
+		assert_eq!(Proposals::<T>::count(), 0);
+	}
+
+	unlock {
+		let index = propose::<T>(&funded::<T>(account("proposer", 0, 0)))?;
+		let caller = funded::<T>(whitelisted_caller());
+		QuadraticVoting::<T>::vote(RawOrigin::Signed(caller.clone()).into(), index, true, 10)?;
+		Proposals::<T>::remove(index);
+	}: _(RawOrigin::Signed(caller.clone()), index)
+	verify {
+		assert!(!Voting::<T>::contains_key(index, &caller));
+	}
+
+	impl_benchmark_test_suite!(QuadraticVoting, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,363 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Quadratic Voting Pallet
+//!
+//! Decides on proposals to dispatch a call by quadratic voting.
+//!
+//! Any account may propose a call that `CallFilter` allows, holding `ProposalDeposit` while the
+//! proposal is open. For `VotingPeriod` blocks, accounts may then vote for or against it once
+//! each, casting `n` votes for `n * n * VoteUnit` reserved tokens.
+//!
+//! The proposal is tallied in `on_initialize` of the block in which its voting period ends. It
+//! passes if at least `MinTurnout` tokens were reserved for votes on it, and more of the votes are
+//! for it than against it. The call of a passed proposal is dispatched with the root origin in the
+//! same block. The proposal deposit is returned either way, and voters unlock their tokens with
+//! `unlock` once the proposal is tallied.
+//!
+//! The turnout is counted in tokens rather than in accounts or votes: splitting the same tokens
+//! across more accounts buys more votes, but does not add to the turnout.
+//!
+//! At most `MaxEndingPerBlock` proposals end in any block, so `on_initialize` dispatches at most
+//! `MaxEndingPerBlock` calls of up to `MaxProposalWeight` each. The runtime must leave room for
+//! that much weight in every block.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
+/// The log target of this pallet.
+const LOG_TARGET: &str = "runtime::quadratic-voting";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::WeightInfo;
+	use frame_support::{
+		dispatch::{GetDispatchInfo, PostDispatchInfo},
+		pallet_prelude::*,
+		traits::{Contains, Currency, ReservableCurrency},
+		weights::extract_actual_weight,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Dispatchable, Saturating, Zero};
+	use sp_std::boxed::Box;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	/// The balance type of the currency in which votes are bought.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The index of a proposal.
+	pub type ProposalIndex = u32;
+
+	/// A proposal, as recorded in `Proposals`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ProposalInfo<AccountId, Call, BlockNumber, Balance> {
+		/// The account that made the proposal.
+		pub proposer: AccountId,
+		/// The call dispatched with the root origin if the proposal passes.
+		pub call: Box<Call>,
+		/// The block in which the voting period ends and the proposal is tallied.
+		pub end: BlockNumber,
+		/// The deposit held from the proposer until the proposal is tallied.
+		pub deposit: Balance,
+		/// The number of votes for the proposal.
+		pub ayes: u32,
+		/// The number of votes against the proposal.
+		pub nays: u32,
+		/// The tokens reserved for the votes on the proposal, for and against.
+		pub turnout: Balance,
+	}
+
+	/// The `ProposalInfo` of a runtime.
+	pub type ProposalInfoOf<T> = ProposalInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::Proposal,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
+
+	/// The votes of an account on a proposal, as recorded in `Voting`.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Vote<Balance> {
+		/// Whether the votes are for the proposal.
+		pub aye: bool,
+		/// The number of votes.
+		pub votes: u32,
+		/// The tokens reserved to buy the votes.
+		pub reserved: Balance,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The currency in which votes are bought.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The call that may be proposed.
+		type Proposal: Parameter
+			+ Dispatchable<
+				Origin = <Self as frame_system::Config>::Origin,
+				PostInfo = PostDispatchInfo,
+			> + GetDispatchInfo;
+		/// The calls that may be proposed.
+		type CallFilter: Contains<Self::Proposal>;
+		/// The number of blocks a proposal is open for votes.
+		#[pallet::constant]
+		type VotingPeriod: Get<Self::BlockNumber>;
+		/// The deposit held from the proposer while a proposal is open.
+		#[pallet::constant]
+		type ProposalDeposit: Get<BalanceOf<Self>>;
+		/// The price of a single vote. Casting `n` votes costs `n * n` times as much.
+		#[pallet::constant]
+		type VoteUnit: Get<BalanceOf<Self>>;
+		/// The minimum number of tokens that must be reserved for votes on a proposal, for and
+		/// against, for it to pass.
+		#[pallet::constant]
+		type MinTurnout: Get<BalanceOf<Self>>;
+		/// The maximum number of open proposals.
+		#[pallet::constant]
+		type MaxProposals: Get<u32>;
+		/// The maximum number of proposals whose voting period ends in the same block.
+		#[pallet::constant]
+		type MaxEndingPerBlock: Get<u32>;
+		/// The maximum weight of a proposed call. `on_initialize` dispatches up to
+		/// `MaxEndingPerBlock` such calls in a block.
+		#[pallet::constant]
+		type MaxProposalWeight: Get<Weight>;
+		/// A call that `CallFilter` allows, which the benchmarks propose.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkProposal: Get<Self::Proposal>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	// The index of the next proposal.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_count)]
+	pub type ProposalCount<T> = StorageValue<_, ProposalIndex, ValueQuery>;
+
+	// The open proposals. A counted map keeps track of how many are open.
+	#[pallet::storage]
+	#[pallet::getter(fn proposals)]
+	pub type Proposals<T: Config> =
+		CountedStorageMap<_, Twox64Concat, ProposalIndex, ProposalInfoOf<T>>;
+
+	// The open proposals whose voting period ends in each block.
+	#[pallet::storage]
+	#[pallet::getter(fn ending)]
+	pub type Ending<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<ProposalIndex, T::MaxEndingPerBlock>,
+		ValueQuery,
+	>;
+
+	// The votes of each account on each proposal, kept until the account unlocks its tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn voting)]
+	pub type Voting<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ProposalIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		Vote<BalanceOf<T>>,
+	>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Tally the proposals whose voting period ends in this block, return their deposits and
+		/// dispatch the calls of the ones that passed.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ending = <Ending<T>>::take(now);
+			let mut weight = T::WeightInfo::on_initialize(ending.len() as u32);
+
+			for index in ending {
+				if let Some(proposal) = <Proposals<T>>::take(index) {
+					T::Currency::unreserve(&proposal.proposer, proposal.deposit);
+					weight = weight.saturating_add(Self::enact(index, proposal));
+				}
+			}
+			weight
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A proposal was made. [index, proposer, end]
+		Proposed(ProposalIndex, T::AccountId, T::BlockNumber),
+		/// An account voted on a proposal. [who, index, aye, votes, reserved]
+		Voted(T::AccountId, ProposalIndex, bool, u32, BalanceOf<T>),
+		/// A proposal passed and its call was dispatched with the root origin. [index, result]
+		Passed(ProposalIndex, DispatchResult),
+		/// A proposal was rejected, for lack of a turnout or majority. [index]
+		Rejected(ProposalIndex),
+		/// An account unlocked the tokens of its votes on a tallied proposal. [who, index, amount]
+		Unlocked(T::AccountId, ProposalIndex, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There are already `MaxProposals` open proposals.
+		TooManyProposals,
+		/// `CallFilter` does not allow the proposed call.
+		CallNotAllowed,
+		/// The proposed call weighs more than `MaxProposalWeight`.
+		ProposalTooHeavy,
+		/// The proposal does not exist or its vote is over.
+		ProposalNotOpen,
+		/// The proposal has not been tallied yet.
+		ProposalStillOpen,
+		/// At least one vote must be cast.
+		ZeroVotes,
+		/// The account has already voted on the proposal.
+		AlreadyVoted,
+		/// The account has not voted on the proposal.
+		NotVoted,
+		/// `MaxEndingPerBlock` proposals already end in the block this one would end in.
+		TooManyEnding,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Propose to dispatch `call` with the root origin, holding `ProposalDeposit` from the
+		/// caller until the proposal is tallied at the end of its voting period.
+		#[pallet::weight(T::WeightInfo::propose())]
+		pub fn propose(origin: OriginFor<T>, call: Box<T::Proposal>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<Proposals<T>>::count() < T::MaxProposals::get(), Error::<T>::TooManyProposals);
+			ensure!(T::CallFilter::contains(&call), Error::<T>::CallNotAllowed);
+			ensure!(
+				call.get_dispatch_info().weight <= T::MaxProposalWeight::get(),
+				Error::<T>::ProposalTooHeavy
+			);
+
+			let index = <ProposalCount<T>>::get();
+			let end =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::VotingPeriod::get());
+			<Ending<T>>::try_append(end, index).map_err(|_| Error::<T>::TooManyEnding)?;
+
+			let deposit = T::ProposalDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+
+			<ProposalCount<T>>::put(index.wrapping_add(1));
+			let proposal = ProposalInfo {
+				proposer: who.clone(),
+				call,
+				end,
+				deposit,
+				ayes: 0,
+				nays: 0,
+				turnout: Zero::zero(),
+			};
+			<Proposals<T>>::insert(index, proposal);
+
+			Self::deposit_event(Event::Proposed(index, who, end));
+			Ok(())
+		}
+
+		/// Cast `votes` votes for or against a proposal in its voting period, reserving `votes *
+		/// votes * VoteUnit` from the caller until the proposal is tallied.
+		///
+		/// An account votes at most once on each proposal.
+		#[pallet::weight(T::WeightInfo::vote())]
+		pub fn vote(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ProposalIndex,
+			aye: bool,
+			votes: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(votes > 0, Error::<T>::ZeroVotes);
+			let mut proposal = <Proposals<T>>::get(index).ok_or(Error::<T>::ProposalNotOpen)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < proposal.end,
+				Error::<T>::ProposalNotOpen
+			);
+			ensure!(!<Voting<T>>::contains_key(index, &who), Error::<T>::AlreadyVoted);
+
+			let reserved = Self::vote_cost(votes);
+			T::Currency::reserve(&who, reserved)?;
+			<Voting<T>>::insert(index, &who, Vote { aye, votes, reserved });
+
+			if aye {
+				proposal.ayes = proposal.ayes.saturating_add(votes);
+			} else {
+				proposal.nays = proposal.nays.saturating_add(votes);
+			}
+			proposal.turnout = proposal.turnout.saturating_add(reserved);
+			<Proposals<T>>::insert(index, proposal);
+
+			Self::deposit_event(Event::Voted(who, index, aye, votes, reserved));
+			Ok(())
+		}
+
+		/// Unlock the tokens the caller reserved to vote on a proposal that was tallied.
+		#[pallet::weight(T::WeightInfo::unlock())]
+		pub fn unlock(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ProposalIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!<Proposals<T>>::contains_key(index), Error::<T>::ProposalStillOpen);
+			let vote = <Voting<T>>::take(index, &who).ok_or(Error::<T>::NotVoted)?;
+
+			T::Currency::unreserve(&who, vote.reserved);
+
+			Self::deposit_event(Event::Unlocked(who, index, vote.reserved));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The tokens reserved to cast `votes` votes.
+		pub fn vote_cost(votes: u32) -> BalanceOf<T> {
+			let votes = BalanceOf::<T>::from(votes);
+			T::VoteUnit::get().saturating_mul(votes).saturating_mul(votes)
+		}
+
+		/// Whether `proposal` passed: enough tokens were reserved for votes on it, and more of the
+		/// votes are for it than against it.
+		pub fn is_passed(proposal: &ProposalInfoOf<T>) -> bool {
+			proposal.turnout >= T::MinTurnout::get() && proposal.ayes > proposal.nays
+		}
+
+		/// Dispatch the call of a tallied proposal with the root origin if it passed.
+		///
+		/// Returns the weight of the dispatched call.
+		fn enact(index: ProposalIndex, proposal: ProposalInfoOf<T>) -> Weight {
+			if !Self::is_passed(&proposal) {
+				Self::deposit_event(Event::Rejected(index));
+				return 0
+			}
+
+			let call = *proposal.call;
+			let info = call.get_dispatch_info();
+			let result = call.dispatch(frame_system::RawOrigin::Root.into());
+			let weight = extract_actual_weight(&result, &info);
+
+			Self::deposit_event(Event::Passed(index, result.map(|_| ()).map_err(|e| e.error)));
+			weight
+		}
+	}
+}
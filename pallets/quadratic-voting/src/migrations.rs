@@ -0,0 +1,130 @@
+//! Storage migrations for the quadratic voting pallet.
+//!
+//! Every change to the layout of the pallet's storage bumps `STORAGE_VERSION` in `lib.rs` and adds
+//! a module here with an `OnRuntimeUpgrade` implementation that brings the storage of a live chain
+//! up to date. The runtime registers the migrations it needs in its `Migrations` tuple.
+
+use super::*;
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::traits::{One, Saturating, Zero};
+use sp_std::{boxed::Box, marker::PhantomData};
+
+pub mod v1 {
+	use super::*;
+	use codec::Decode;
+
+	/// A proposal as recorded in `Proposals` up to version 0, when proposals were closed with
+	/// `close` and needed a quorum of accounts.
+	#[derive(Decode)]
+	struct OldProposalInfo<AccountId, Call, BlockNumber, Balance> {
+		proposer: AccountId,
+		call: Box<Call>,
+		end: BlockNumber,
+		deposit: Balance,
+		ayes: u32,
+		nays: u32,
+		#[allow(dead_code)]
+		voters: u32,
+	}
+
+	/// Migrates the pallet's storage from version 0 to version 1.
+	///
+	/// Version 1 tallies proposals in `on_initialize` of the block in which their voting period
+	/// ends, as indexed in `Ending`, and counts their turnout in reserved tokens. The turnout of
+	/// every open proposal is summed from its votes, and the proposal is indexed in `Ending` at
+	/// the end of its voting period. A proposal whose voting period is already over, because
+	/// nobody closed it, is tallied in the next block instead. If `MaxEndingPerBlock` proposals
+	/// already end in that block, it is tallied in the first block after it with room.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+			if on_chain_version == 0 {
+				let next_block =
+					<frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+				let (mut proposals, mut accesses) = (0u64, 0u64);
+				Proposals::<T>::translate::<
+					OldProposalInfo<T::AccountId, T::Proposal, T::BlockNumber, BalanceOf<T>>,
+					_,
+				>(|index, old| {
+					proposals += 1;
+					let mut turnout = BalanceOf::<T>::zero();
+					for vote in Voting::<T>::iter_prefix_values(index) {
+						turnout = turnout.saturating_add(vote.reserved);
+						accesses += 1;
+					}
+					let mut end = old.end.max(next_block);
+					while Ending::<T>::try_append(end, index).is_err() {
+						end = end.saturating_add(One::one());
+						accesses += 1;
+					}
+					Some(ProposalInfo {
+						proposer: old.proposer,
+						call: old.call,
+						end,
+						deposit: old.deposit,
+						ayes: old.ayes,
+						nays: old.nays,
+						turnout,
+					})
+				});
+				StorageVersion::new(1).put::<Pallet<T>>();
+				log::info!(
+					target: LOG_TARGET,
+					"migrated storage to version 1, indexed {} open proposals",
+					proposals
+				);
+				T::DbWeight::get()
+					.reads_writes(2 * proposals + accesses + 2, 2 * proposals + accesses + 1)
+			} else {
+				log::warn!(
+					target: LOG_TARGET,
+					"skipping migration to version 1, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 0,
+				"the storage version must be 0 before migrating to version 1"
+			);
+			Self::set_temp_storage(Proposals::<T>::count(), "proposals");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"the storage version must be 1 after the migration"
+			);
+			let proposals: u32 = Self::get_temp_storage("proposals")
+				.ok_or("the number of proposals was not recorded before the migration")?;
+			frame_support::ensure!(
+				Proposals::<T>::iter().count() as u32 == proposals,
+				"the migration must keep every open proposal"
+			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			for (index, proposal) in Proposals::<T>::iter() {
+				frame_support::ensure!(
+					proposal.end > now && Ending::<T>::get(proposal.end).contains(&index),
+					"every open proposal must be tallied at the end of its voting period"
+				);
+			}
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,170 @@
+use crate as pallet_quadratic_voting;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, Contains};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// A pallet with a value that only root may set.
+#[frame_support::pallet]
+pub mod admin {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn value)]
+	pub type Value<T> = StorageValue<_, u32>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(0)]
+		pub fn set_value(origin: OriginFor<T>, value: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			<Value<T>>::put(value);
+			Ok(())
+		}
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		QuadraticVoting: pallet_quadratic_voting::{Pallet, Call, Storage, Event<T>},
+		Admin: admin::{Pallet, Call, Storage},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// The balance of the accounts endowed at genesis.
+pub const ENDOWMENT: u64 = 100;
+
+/// The deposit held for each proposal.
+pub const DEPOSIT: u64 = 10;
+
+/// The number of blocks a proposal is open for votes.
+pub const VOTING_PERIOD: u64 = 5;
+
+/// The maximum number of open proposals.
+pub const MAX_PROPOSALS: u32 = 3;
+
+/// The maximum number of proposals whose voting period ends in the same block.
+pub const MAX_ENDING_PER_BLOCK: u32 = 2;
+
+/// The maximum weight of a proposed call.
+pub const MAX_PROPOSAL_WEIGHT: u64 = 1_000_000_000;
+
+/// The minimum number of tokens that must be reserved for votes on a proposal for it to pass.
+pub const MIN_TURNOUT: u64 = 10;
+
+impl admin::Config for Test {
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+}
+
+/// Balances calls may not be proposed.
+pub struct NoBalancesCalls;
+
+impl Contains<Call> for NoBalancesCalls {
+	fn contains(call: &Call) -> bool {
+		!matches!(call, Call::Balances(..))
+	}
+}
+
+impl pallet_quadratic_voting::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type Proposal = Call;
+	type CallFilter = NoBalancesCalls;
+	type VotingPeriod = ConstU64<VOTING_PERIOD>;
+	type ProposalDeposit = ConstU64<DEPOSIT>;
+	type VoteUnit = ConstU64<1>;
+	type MinTurnout = ConstU64<MIN_TURNOUT>;
+	type MaxProposals = ConstU32<MAX_PROPOSALS>;
+	type MaxEndingPerBlock = ConstU32<MAX_ENDING_PER_BLOCK>;
+	type MaxProposalWeight = ConstU64<MAX_PROPOSAL_WEIGHT>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkProposal = SetValue;
+	type WeightInfo = ();
+}
+
+/// The call the benchmarks propose.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct SetValue;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl frame_support::traits::Get<Call> for SetValue {
+	fn get() -> Call {
+		Call::Admin(admin::Call::set_value { value: 42 })
+	}
+}
+
+// Build genesis storage according to the mock runtime. Accounts 1 to 3 are endowed.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig { balances: (1..=3).map(|who| (who, ENDOWMENT)).collect() },
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	// Events are not deposited in block 0.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,256 @@
+use crate::{migrations, mock::*, Ending, Error, Event as VotingEvent, Proposals, Vote, Voting};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchError,
+	storage::unhashed,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::Perbill;
+
+/// A call that needs the root origin: set the value of the admin pallet.
+fn set_value(value: u32) -> Box<Call> {
+	Box::new(Call::Admin(admin::Call::set_value { value }))
+}
+
+/// Vote on proposal `index` with enough tokens and votes to pass it.
+fn pass(index: u32) {
+	assert_ok!(QuadraticVoting::vote(Origin::signed(2), index, true, 3));
+	assert_ok!(QuadraticVoting::vote(Origin::signed(3), index, true, 1));
+}
+
+/// Move to block `n` and run `on_initialize` in it.
+fn run_to(n: u64) {
+	System::set_block_number(n);
+	QuadraticVoting::on_initialize(n);
+}
+
+#[test]
+fn propose_holds_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+
+		let proposal = QuadraticVoting::proposals(0).unwrap();
+		assert_eq!(proposal.proposer, 1);
+		assert_eq!(proposal.end, 1 + VOTING_PERIOD);
+		assert_eq!(QuadraticVoting::ending(1 + VOTING_PERIOD).into_inner(), vec![0]);
+		assert_eq!(Balances::reserved_balance(1), DEPOSIT);
+		assert_eq!(QuadraticVoting::proposal_count(), 1);
+		System::assert_last_event(VotingEvent::Proposed(0, 1, 1 + VOTING_PERIOD).into());
+	});
+}
+
+#[test]
+fn open_proposals_are_bounded() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MAX_ENDING_PER_BLOCK {
+			assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+		}
+		assert_noop!(
+			QuadraticVoting::propose(Origin::signed(2), set_value(42)),
+			Error::<Test>::TooManyEnding
+		);
+
+		System::set_block_number(2);
+		for _ in MAX_ENDING_PER_BLOCK..MAX_PROPOSALS {
+			assert_ok!(QuadraticVoting::propose(Origin::signed(2), set_value(42)));
+		}
+		assert_noop!(
+			QuadraticVoting::propose(Origin::signed(2), set_value(42)),
+			Error::<Test>::TooManyProposals
+		);
+
+		// Tallying proposals makes room for new ones.
+		run_to(1 + VOTING_PERIOD);
+		assert_ok!(QuadraticVoting::propose(Origin::signed(3), set_value(42)));
+	});
+}
+
+#[test]
+fn only_allowed_calls_can_be_proposed() {
+	new_test_ext().execute_with(|| {
+		let transfer =
+			Box::new(Call::Balances(pallet_balances::Call::transfer { dest: 2, value: 1 }));
+		assert_noop!(
+			QuadraticVoting::propose(Origin::signed(1), transfer),
+			Error::<Test>::CallNotAllowed
+		);
+	});
+}
+
+#[test]
+fn heavy_calls_cannot_be_proposed() {
+	new_test_ext().execute_with(|| {
+		let heavy =
+			Box::new(Call::System(frame_system::Call::fill_block { ratio: Perbill::one() }));
+		assert_noop!(
+			QuadraticVoting::propose(Origin::signed(1), heavy),
+			Error::<Test>::ProposalTooHeavy
+		);
+	});
+}
+
+#[test]
+fn votes_cost_the_square_of_their_number() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+
+		assert_ok!(QuadraticVoting::vote(Origin::signed(2), 0, true, 3));
+		assert_eq!(Balances::reserved_balance(2), 9);
+		assert_eq!(QuadraticVoting::voting(0, 2), Some(Vote { aye: true, votes: 3, reserved: 9 }));
+		System::assert_last_event(VotingEvent::Voted(2, 0, true, 3, 9).into());
+
+		assert_ok!(QuadraticVoting::vote(Origin::signed(3), 0, false, 2));
+		let proposal = QuadraticVoting::proposals(0).unwrap();
+		assert_eq!((proposal.ayes, proposal.nays, proposal.turnout), (3, 2, 13));
+	});
+}
+
+#[test]
+fn votes_are_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			QuadraticVoting::vote(Origin::signed(2), 0, true, 1),
+			Error::<Test>::ProposalNotOpen
+		);
+
+		assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+		assert_noop!(
+			QuadraticVoting::vote(Origin::signed(2), 0, true, 0),
+			Error::<Test>::ZeroVotes
+		);
+		// 11 votes cost 121, more than the account has.
+		assert_noop!(
+			QuadraticVoting::vote(Origin::signed(2), 0, true, 11),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(QuadraticVoting::vote(Origin::signed(2), 0, true, 1));
+		assert_noop!(
+			QuadraticVoting::vote(Origin::signed(2), 0, false, 1),
+			Error::<Test>::AlreadyVoted
+		);
+
+		// Votes are not accepted in the block in which the voting period ends.
+		System::set_block_number(1 + VOTING_PERIOD);
+		assert_noop!(
+			QuadraticVoting::vote(Origin::signed(3), 0, true, 1),
+			Error::<Test>::ProposalNotOpen
+		);
+	});
+}
+
+#[test]
+fn proposals_are_tallied_when_their_voting_period_ends() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+		pass(0);
+
+		run_to(VOTING_PERIOD);
+		assert!(QuadraticVoting::proposals(0).is_some());
+		assert_eq!(Admin::value(), None);
+
+		run_to(1 + VOTING_PERIOD);
+		assert_eq!(QuadraticVoting::proposals(0), None);
+		assert!(QuadraticVoting::ending(1 + VOTING_PERIOD).is_empty());
+		assert_eq!(Admin::value(), Some(42));
+	});
+}
+
+#[test]
+fn passed_proposals_are_dispatched_with_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Admin::set_value(Origin::signed(1), 42), DispatchError::BadOrigin);
+
+		assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+		assert_ok!(QuadraticVoting::vote(Origin::signed(2), 0, true, 3));
+		assert_ok!(QuadraticVoting::vote(Origin::signed(3), 0, false, 2));
+
+		run_to(1 + VOTING_PERIOD);
+		assert_eq!(Admin::value(), Some(42));
+		System::assert_has_event(VotingEvent::Passed(0, Ok(())).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn proposals_without_a_majority_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+		// Two accounts with one vote each are outvoted by one account with three votes.
+		assert_ok!(QuadraticVoting::vote(Origin::signed(1), 0, true, 1));
+		assert_ok!(QuadraticVoting::vote(Origin::signed(2), 0, true, 1));
+		assert_ok!(QuadraticVoting::vote(Origin::signed(3), 0, false, 3));
+
+		run_to(1 + VOTING_PERIOD);
+		assert_eq!(Admin::value(), None);
+		System::assert_last_event(VotingEvent::Rejected(0).into());
+		assert_eq!(Balances::reserved_balance(1), 1);
+	});
+}
+
+#[test]
+fn proposals_without_a_turnout_are_rejected() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..MAX_ENDING_PER_BLOCK {
+			assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+		}
+		// More accounts do not make up for too few tokens: two accounts reserve 2.
+		assert_ok!(QuadraticVoting::vote(Origin::signed(2), 0, true, 1));
+		assert_ok!(QuadraticVoting::vote(Origin::signed(3), 0, true, 1));
+		// A single account that reserves enough tokens makes the turnout.
+		assert_ok!(QuadraticVoting::vote(Origin::signed(2), 1, true, 4));
+
+		run_to(1 + VOTING_PERIOD);
+		System::assert_has_event(VotingEvent::Rejected(0).into());
+		System::assert_has_event(VotingEvent::Passed(1, Ok(())).into());
+	});
+}
+
+#[test]
+fn votes_are_unlocked_after_the_vote() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::propose(Origin::signed(1), set_value(42)));
+		assert_ok!(QuadraticVoting::vote(Origin::signed(2), 0, true, 3));
+		assert_noop!(
+			QuadraticVoting::unlock(Origin::signed(2), 0),
+			Error::<Test>::ProposalStillOpen
+		);
+
+		run_to(1 + VOTING_PERIOD);
+		assert_noop!(QuadraticVoting::unlock(Origin::signed(3), 0), Error::<Test>::NotVoted);
+		assert_ok!(QuadraticVoting::unlock(Origin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(QuadraticVoting::voting(0, 2), None);
+		System::assert_last_event(VotingEvent::Unlocked(2, 0, 9).into());
+		assert_noop!(QuadraticVoting::unlock(Origin::signed(2), 0), Error::<Test>::NotVoted);
+	});
+}
+
+#[test]
+fn migration_to_v1_indexes_the_open_proposals() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<QuadraticVoting>();
+		// Two proposals in the layout of version 0: one whose voting period is over but which
+		// nobody closed, and one that is still open, both with a vote on them.
+		System::set_block_number(10);
+		for (index, end) in [(0u32, 5u64), (1, 12)] {
+			let old = (1u64, set_value(42), end, DEPOSIT, 3u32, 0u32, 1u32);
+			unhashed::put_raw(&Proposals::<Test>::hashed_key_for(index), &old.encode());
+			Voting::<Test>::insert(index, 2, Vote { aye: true, votes: 3, reserved: 9 });
+		}
+		Proposals::<Test>::initialize_counter();
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			QuadraticVoting::on_chain_storage_version(),
+			QuadraticVoting::current_storage_version()
+		);
+		let proposal = QuadraticVoting::proposals(0).unwrap();
+		assert_eq!((proposal.end, proposal.turnout), (11, 9));
+		assert_eq!(QuadraticVoting::proposals(1).unwrap().end, 12);
+		assert_eq!(Ending::<Test>::get(11).into_inner(), vec![0]);
+		assert_eq!(Ending::<Test>::get(12).into_inner(), vec![1]);
+	});
+}
@@ -0,0 +1,106 @@
+
+//! Estimated weights for pallet_quadratic_voting
+//!
+//! These weights are hand-written estimates, not benchmark results: the storage accesses follow
+//! each call's implementation, and the execution times are scaled from comparable FRAME
+//! pallets. Replace them with `./scripts/benchmark.sh pallet_quadratic_voting` on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_quadratic_voting.
+pub trait WeightInfo {
+	fn on_initialize(n: u32, ) -> Weight;
+	fn propose() -> Weight;
+	fn vote() -> Weight;
+	fn unlock() -> Weight;
+}
+
+/// Weights for pallet_quadratic_voting using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: QuadraticVoting Ending (r:1 w:1)
+	// Storage: QuadraticVoting Proposals (r:1 w:1)
+	// Storage: QuadraticVoting CounterForProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn on_initialize(n: u32, ) -> Weight {
+		(3_962_000 as Weight)
+			.saturating_add((31_204_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: QuadraticVoting Ending (r:1 w:1)
+	// Storage: QuadraticVoting CounterForProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: QuadraticVoting ProposalCount (r:1 w:1)
+	// Storage: QuadraticVoting Proposals (r:0 w:1)
+	fn propose() -> Weight {
+		(39_873_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: QuadraticVoting Proposals (r:1 w:1)
+	// Storage: QuadraticVoting Voting (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn vote() -> Weight {
+		(38_311_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting Proposals (r:1 w:0)
+	// Storage: QuadraticVoting Voting (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock() -> Weight {
+		(31_872_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: QuadraticVoting Ending (r:1 w:1)
+	// Storage: QuadraticVoting Proposals (r:1 w:1)
+	// Storage: QuadraticVoting CounterForProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn on_initialize(n: u32, ) -> Weight {
+		(3_962_000 as Weight)
+			.saturating_add((31_204_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: QuadraticVoting Ending (r:1 w:1)
+	// Storage: QuadraticVoting CounterForProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: QuadraticVoting ProposalCount (r:1 w:1)
+	// Storage: QuadraticVoting Proposals (r:0 w:1)
+	fn propose() -> Weight {
+		(39_873_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: QuadraticVoting Proposals (r:1 w:1)
+	// Storage: QuadraticVoting Voting (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn vote() -> Weight {
+		(38_311_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting Proposals (r:1 w:0)
+	// Storage: QuadraticVoting Voting (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock() -> Weight {
+		(31_872_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
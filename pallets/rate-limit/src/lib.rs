@@ -0,0 +1,200 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Rate Limit Pallet
+//!
+//! A signed extension that limits how many transactions an account may submit per period.
+//!
+//! The runtime sorts its calls into categories with a `RateLimits` implementation. Each category
+//! has its own limit of `max` transactions per `period` blocks, counted per account in
+//! `RateLimitCount`. Periods are aligned to multiples of `period`, so every account's count of a
+//! category starts over in the same block.
+//!
+//! An account's count is kept with the block in which its period ends, and a count whose period
+//! has ended is overwritten by the account's next transaction in the category. Counts are never
+//! removed in bulk, so no block has to pay for removing the counts of every account that
+//! transacted in a period.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::Get, Parameter};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, One, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	RuntimeDebug,
+};
+use sp_std::marker::PhantomData;
+
+/// The `InvalidTransaction::Custom` code of a transaction rejected by `CheckRateLimit`.
+pub const RATE_LIMITED: u8 = 1;
+
+/// The rate limit of a category of calls.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RateLimit<BlockNumber> {
+	/// The number of transactions an account may submit per period.
+	pub max: u32,
+	/// The length of a period in blocks. A period of zero is treated as a single block.
+	pub period: BlockNumber,
+}
+
+/// Sorts the calls of a runtime into rate-limited categories.
+pub trait RateLimits<Call, BlockNumber> {
+	/// A category of calls that share a rate limit.
+	type Category: Parameter + MaxEncodedLen;
+
+	/// The category of `call` and its rate limit, or `None` if `call` is not rate-limited.
+	fn rate_limit(call: &Call) -> Option<(Self::Category, RateLimit<BlockNumber>)>;
+}
+
+/// No call is rate-limited.
+impl<Call, BlockNumber> RateLimits<Call, BlockNumber> for () {
+	type Category = ();
+
+	fn rate_limit(_call: &Call) -> Option<((), RateLimit<BlockNumber>)> {
+		None
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::RateLimits;
+	use frame_support::pallet_prelude::*;
+
+	/// The category of calls that share a rate limit, as sorted by `Config::RateLimits`.
+	pub type RateLimitCategoryOf<T> = <<T as Config>::RateLimits as RateLimits<
+		<T as frame_system::Config>::Call,
+		<T as frame_system::Config>::BlockNumber,
+	>>::Category;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The rate limits that `CheckRateLimit` enforces on the transactions of each account.
+		type RateLimits: RateLimits<<Self as frame_system::Config>::Call, Self::BlockNumber>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	// The block in which the last rate limit period an account transacted in a category of calls
+	// ends, and the number of transactions it submitted in the category during that period.
+	#[pallet::storage]
+	pub type RateLimitCount<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(T::AccountId, RateLimitCategoryOf<T>),
+		(T::BlockNumber, u32),
+	>;
+}
+
+/// Reject the transactions of an account that has used up the rate limit of the category of the
+/// call, as given by `Config::RateLimits`.
+///
+/// Excess transactions are rejected in `validate`, so they never enter the transaction pool. A
+/// transaction is only counted once it is included in a block, so an account may still have more
+/// transactions than its limit in the pool; the excess ones are dropped when they are revalidated
+/// after the limit has been used up.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckRateLimit<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckRateLimit<T> {
+	/// Create new `SignedExtension` to check the rate limits of the calls.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	/// The block in which the current period of `limit` ends and the number of transactions
+	/// `who` submitted in `category` during it.
+	///
+	/// Fails if `who` has already used up `limit`.
+	fn count(
+		who: &T::AccountId,
+		category: &RateLimitCategoryOf<T>,
+		limit: &RateLimit<T::BlockNumber>,
+	) -> Result<(T::BlockNumber, u32), TransactionValidityError> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let period = limit.period.max(One::one());
+		let end = (now - now % period).saturating_add(period);
+		let count = match <RateLimitCount<T>>::get((who.clone(), category.clone())) {
+			Some((counted_end, count)) if counted_end == end => count,
+			// The count of a period that has ended no longer limits anything.
+			_ => 0,
+		};
+
+		if count >= limit.max {
+			return Err(InvalidTransaction::Custom(RATE_LIMITED).into())
+		}
+		Ok((end, count))
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckRateLimit<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckRateLimit<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckRateLimit")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckRateLimit<T> {
+	const IDENTIFIER: &'static str = "CheckRateLimit";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if let Some((category, limit)) = T::RateLimits::rate_limit(call) {
+			Self::count(who, &category, &limit)?;
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<(), TransactionValidityError> {
+		if let Some((category, limit)) = T::RateLimits::rate_limit(call) {
+			let (end, count) = Self::count(who, &category, &limit)?;
+			<RateLimitCount<T>>::insert((who.clone(), category), (end, count + 1));
+			// The weight of the call does not include reading and writing its count.
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				T::DbWeight::get().reads_writes(1, 1),
+				info.class,
+			);
+		}
+		Ok(())
+	}
+}
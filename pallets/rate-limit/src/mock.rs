@@ -0,0 +1,79 @@
+use crate as pallet_rate_limit;
+use crate::{RateLimit, RateLimits};
+use frame_support::traits::{ConstU16, ConstU64};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		RateLimit: pallet_rate_limit::{Pallet, Storage},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+/// The rate limit of the calls that make a remark.
+pub const REMARK_RATE_LIMIT: RateLimit<u64> = RateLimit { max: 2, period: 4 };
+
+/// Limits the calls that make a remark, and nothing else.
+pub struct TestRateLimits;
+
+impl RateLimits<Call, u64> for TestRateLimits {
+	type Category = ();
+
+	fn rate_limit(call: &Call) -> Option<((), RateLimit<u64>)> {
+		match call {
+			Call::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. },
+			) => Some(((), REMARK_RATE_LIMIT)),
+			_ => None,
+		}
+	}
+}
+
+impl pallet_rate_limit::Config for Test {
+	type RateLimits = TestRateLimits;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+}
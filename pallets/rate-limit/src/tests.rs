@@ -0,0 +1,57 @@
+use crate::{mock::*, CheckRateLimit, RateLimitCount, RATE_LIMITED};
+use frame_support::{assert_ok, dispatch::DispatchInfo};
+use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
+
+#[test]
+fn excess_transactions_are_rate_limited() {
+	new_test_ext().execute_with(|| {
+		let info = DispatchInfo::default();
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		let check = |who, call: &Call| CheckRateLimit::<Test>::new().validate(&who, call, &info, 0);
+		let dispatch =
+			|who, call: &Call| CheckRateLimit::<Test>::new().pre_dispatch(&who, call, &info, 0);
+		let rate_limited = InvalidTransaction::Custom(RATE_LIMITED);
+
+		System::set_block_number(1);
+		for _ in 0..REMARK_RATE_LIMIT.max {
+			assert_ok!(check(1, &remark));
+			assert_ok!(dispatch(1, &remark));
+		}
+		assert_eq!(check(1, &remark), rate_limited.into());
+		assert_eq!(dispatch(1, &remark), Err(rate_limited.into()));
+
+		// The calls share the limit of their category, other calls and accounts are unaffected.
+		let remark_with_event =
+			Call::System(frame_system::Call::remark_with_event { remark: vec![] });
+		assert_eq!(check(1, &remark_with_event), rate_limited.into());
+		assert_ok!(check(1, &Call::System(frame_system::Call::set_heap_pages { pages: 1 })));
+		assert_ok!(check(2, &remark));
+	});
+}
+
+#[test]
+fn the_count_starts_over_in_the_next_period() {
+	new_test_ext().execute_with(|| {
+		let info = DispatchInfo::default();
+		let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+		let check = |call: &Call| CheckRateLimit::<Test>::new().validate(&1, call, &info, 0);
+		let dispatch = |call: &Call| CheckRateLimit::<Test>::new().pre_dispatch(&1, call, &info, 0);
+		let period = REMARK_RATE_LIMIT.period;
+
+		System::set_block_number(1);
+		for _ in 0..REMARK_RATE_LIMIT.max {
+			assert_ok!(dispatch(&remark));
+		}
+		assert_eq!(RateLimitCount::<Test>::get((1, ())), Some((period, REMARK_RATE_LIMIT.max)));
+
+		// Periods are aligned to multiples of their length.
+		System::set_block_number(period - 1);
+		assert_eq!(check(&remark), InvalidTransaction::Custom(RATE_LIMITED).into());
+
+		// The count of the period that ended is overwritten by the next transaction.
+		System::set_block_number(period);
+		assert_ok!(check(&remark));
+		assert_ok!(dispatch(&remark));
+		assert_eq!(RateLimitCount::<Test>::get((1, ())), Some((2 * period, 1)));
+	});
+}
@@ -0,0 +1,70 @@
+//! Dispatch arbitrary SCALE-encoded calls of the template pallet and check that its storage stays
+//! consistent.
+//!
+//! The input is a sequence of `(u8, pallet_template::Call)` pairs. The byte picks the origin: root,
+//! none, or one of the accounts 0 to 4. Every call is dispatched in a new block.
+
+#![no_main]
+
+use codec::DecodeLimit;
+use frame_support::{dispatch::UnfilteredDispatchable, traits::Hooks};
+use libfuzzer_sys::fuzz_target;
+use pallet_template::{Call as TemplateCall, SomethingOf};
+use pallet_template_fuzz::*;
+use sp_runtime::{traits::ValidateUnsigned, transaction_validity::TransactionSource};
+
+/// The maximum nesting depth of a decoded call.
+const MAX_DECODE_DEPTH: u32 = 64;
+
+fuzz_target!(|data: &[u8]| {
+	let mut input = data;
+	new_test_ext().execute_with(|| {
+		while let Ok((who, call)) =
+			<(u8, TemplateCall<Runtime>)>::decode_with_depth_limit(MAX_DECODE_DEPTH, &mut input)
+		{
+			let block = System::block_number() + 1;
+			System::set_block_number(block);
+			Scheduler::on_initialize(block);
+			TemplateModule::on_initialize(block);
+
+			let origin = match who {
+				0 => Origin::root(),
+				1 => Origin::none(),
+				n => Origin::signed(u64::from(n % 5)),
+			};
+			// Unsigned calls only reach the pallet if they are valid.
+			if matches!(call, TemplateCall::submit_value_unsigned { .. }) &&
+				TemplateModule::validate_unsigned(TransactionSource::External, &call).is_err()
+			{
+				continue
+			}
+			let _ = call.dispatch_bypass_filter(origin);
+
+			check_invariants();
+		}
+	});
+});
+
+fn check_invariants() {
+	// The aggregate is the sum of the values of all accounts, and there is none without a value.
+	let sum: u64 = SomethingOf::<Runtime>::iter_values().map(u64::from).sum();
+	assert_eq!(sum, TemplateModule::something().unwrap_or_default());
+	if SomethingOf::<Runtime>::count() == 0 {
+		assert_eq!(TemplateModule::something(), None);
+	}
+	assert_eq!(SomethingOf::<Runtime>::count() as usize, SomethingOf::<Runtime>::iter().count());
+
+	// An account only has funds reserved for the deposits of its entry and its scheduled update.
+	for who in 0..5 {
+		let scheduled = TemplateModule::scheduled(who).map(|update| update.deposit);
+		assert_eq!(
+			Balances::reserved_balance(who),
+			TemplateModule::deposit_of(who).unwrap_or_default() + scheduled.unwrap_or_default()
+		);
+		if TemplateModule::deposit_of(who).is_some() {
+			assert!(TemplateModule::something_of(who).is_some());
+		}
+	}
+
+	assert!(TemplateModule::history().len() <= 3);
+}
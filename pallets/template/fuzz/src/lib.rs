@@ -0,0 +1,170 @@
+//! A runtime to fuzz the template pallet with, like the pallet's own mock runtime.
+//!
+//! Accounts 1 to 3 are endowed at genesis; any other account has no balance.
+
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, EqualPrivilegeOnly, SortedMembers};
+use frame_system::offchain::AppCrypto;
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	BuildStorage,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+type Extrinsic = TestXt<Call, ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		TemplateModule: pallet_template::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+	}
+);
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_preimage::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = frame_system::EnsureRoot<u64>;
+	type MaxSize = ConstU32<1024>;
+	type BaseDeposit = ConstU64<2>;
+	type ByteDeposit = ConstU64<1>;
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = ConstU64<1_000_000_000_000>;
+	type ScheduleOrigin = frame_system::EnsureRoot<u64>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type WeightInfo = ();
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = ();
+}
+
+/// Signs with `UintAuthorityId` keys. The account of a key is its number.
+pub struct FuzzAuthId;
+
+impl AppCrypto<UintAuthorityId, TestSignature> for FuzzAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+/// The accounts whose keys may sign unsigned values: all but account 0, including the unfunded
+/// account 4.
+pub struct FuzzOffchainAuthorities;
+
+impl SortedMembers<u64> for FuzzOffchainAuthorities {
+	fn sorted_members() -> Vec<u64> {
+		(1..=4).collect()
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+impl pallet_template::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type StorageDeposit = ConstU64<10>;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type MaxHistory = ConstU32<3>;
+	type HistoryRetention = ConstU64<10>;
+	type AuthorityId = FuzzAuthId;
+	type OffchainAuthorities = FuzzOffchainAuthorities;
+	type OffchainInterval = ConstU64<4>;
+	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+	type ScheduledCall = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type ScheduleDeposit = ConstU64<5>;
+	type MaxUpdatesPerBlock = ConstU32<2>;
+	type WeightInfo = ();
+}
+
+/// Build the genesis storage of the fuzzed runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig { balances: (1..=3).map(|who| (who, 100)).collect() },
+		template_module: Default::default(),
+	}
+	.build_storage()
+	.unwrap()
+	.into()
+}
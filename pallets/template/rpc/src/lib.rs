@@ -0,0 +1,102 @@
+//! RPC interface for the template pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_template_runtime_api::{TemplateApi as TemplateRuntimeApi, TemplateStats};
+
+/// Template pallet RPC methods.
+#[rpc(client, server)]
+pub trait TemplateApi<BlockHash, AccountId> {
+	/// Returns the aggregate of every account's value.
+	#[method(name = "template_something")]
+	fn something(&self, at: Option<BlockHash>) -> RpcResult<Option<u64>>;
+
+	/// Returns the value stored by `who`.
+	#[method(name = "template_somethingOf")]
+	fn something_of(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Option<u32>>;
+
+	/// Returns a summary of the pallet's state.
+	#[method(name = "template_stats")]
+	fn stats(&self, at: Option<BlockHash>) -> RpcResult<TemplateStats>;
+}
+
+/// Provides RPC methods to query the template pallet's state.
+pub struct Template<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Template<C, Block> {
+	/// Create new `Template` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId> TemplateApiServer<<Block as BlockT>::Hash, AccountId>
+	for Template<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: TemplateRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	fn something(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<u64>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.something(&at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn something_of(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u32>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.something_of(&at, who).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn stats(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<TemplateStats> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.stats(&at).map_err(runtime_error_into_rpc_err)
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
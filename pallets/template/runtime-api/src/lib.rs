@@ -0,0 +1,38 @@
+//! Runtime API definition for the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+
+/// A summary of the template pallet's state.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Encode, Decode, TypeInfo, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TemplateStats {
+	/// The aggregate of every account's value, `None` if no account holds a value.
+	pub something: Option<u64>,
+	/// The number of accounts that hold a value.
+	pub accounts: u32,
+	/// Whether the admin origin has frozen writes.
+	pub frozen: bool,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the template pallet's state.
+	pub trait TemplateApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Get the aggregate of every account's value.
+		fn something() -> Option<u64>;
+
+		/// Get the value stored by `who`.
+		fn something_of(who: AccountId) -> Option<u32>;
+
+		/// Get a summary of the pallet's state.
+		fn stats() -> TemplateStats;
+	}
+}
@@ -2,18 +2,185 @@
 
 use super::*;
 
-#[allow(unused)]
 use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use codec::Decode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	traits::{
+		schedule::Named as ScheduleNamed, Currency, EnsureOrigin, Get, Hooks, ReservableCurrency,
+	},
+	BoundedVec,
+};
 use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, IdentifyAccount, Saturating, StaticLookup, TrailingZeroInput};
+
+/// A caller with enough funds to hold the storage deposit.
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// Give `who` an entry in `SomethingOf` that holds the storage deposit.
+fn insert_entry<T: Config>(who: &T::AccountId) -> Result<(), BenchmarkError> {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+	let deposit = T::StorageDeposit::get();
+	T::Currency::reserve(who, deposit)?;
+	DepositOf::<T>::insert(who, deposit);
+	SomethingOf::<T>::insert(who, 100);
+	Something::<T>::put(100);
+	Ok(())
+}
+
+/// Fill `History` up to `MaxHistory` with entries written in `block`, so that the next write has
+/// to drop the oldest one.
+fn fill_history<T: Config>(block: T::BlockNumber) {
+	let who: T::AccountId = account("history", 0, 0);
+	let entry = HistoryEntry { who, value: 0, block };
+	let history = sp_std::vec![entry; T::MaxHistory::get() as usize];
+	History::<T>::put(
+		BoundedVec::try_from(history).expect("history has `MaxHistory` entries; qed"),
+	);
+}
 
 benchmarks! {
 	do_something {
-		let s in 0 .. 100;
+		let s = 100;
+		fill_history::<T>(0u32.into());
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), s)
+	verify {
+		assert_eq!(SomethingOf::<T>::get(&caller), Some(s));
+		assert_eq!(DepositOf::<T>::get(&caller), Some(T::StorageDeposit::get()));
+	}
+
+	cause_error {
+		fill_history::<T>(0u32.into());
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		SomethingOf::<T>::insert(&caller, 100);
+		Something::<T>::put(100);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(SomethingOf::<T>::get(&caller), Some(101));
+	}
+
+	cause_error_none_value {
+		let caller: T::AccountId = whitelisted_caller();
+	}: {
+		assert_eq!(
+			Template::<T>::cause_error(RawOrigin::Signed(caller).into()),
+			Err(Error::<T>::NoneValue.into()),
+		);
+	}
+
+	clear {
+		let caller: T::AccountId = whitelisted_caller();
+		insert_entry::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(SomethingOf::<T>::get(&caller), None);
+		assert_eq!(T::Currency::reserved_balance(&caller), 0u32.into());
+	}
+
+	force_set {
+		fill_history::<T>(0u32.into());
+		let who: T::AccountId = account("who", 0, 0);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, who_lookup, 100)
+	verify {
+		assert_eq!(SomethingOf::<T>::get(&who), Some(100));
+	}
+
+	reset {
+		let who: T::AccountId = account("who", 0, 0);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let origin = T::AdminOrigin::successful_origin();
+		insert_entry::<T>(&who)?;
+	}: _<T::Origin>(origin, who_lookup)
+	verify {
+		assert_eq!(SomethingOf::<T>::get(&who), None);
+		assert_eq!(T::Currency::reserved_balance(&who), 0u32.into());
+	}
+
+	freeze {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin)
+	verify {
+		assert!(Frozen::<T>::get());
+	}
+
+	unfreeze {
+		let origin = T::AdminOrigin::successful_origin();
+		Frozen::<T>::put(true);
+	}: _<T::Origin>(origin)
+	verify {
+		assert!(!Frozen::<T>::get());
+	}
+
+	submit_value_unsigned {
+		fill_history::<T>(0u32.into());
+		// The signature is checked in `validate_unsigned`, not when the call is dispatched.
+		let public = T::Public::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| BenchmarkError::Stop("public key does not decode from zeroes"))?;
+		let signature = T::Signature::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| BenchmarkError::Stop("signature does not decode from zeroes"))?;
+		let who = public.clone().into_account();
+		T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+		let payload = ValuePayload {
+			block_number: frame_system::Pallet::<T>::block_number(),
+			value: 100,
+			public,
+		};
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert_eq!(SomethingOf::<T>::get(&who), Some(100));
+	}
+
+	schedule_set {
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), 100, 10u32.into())
+	verify {
+		assert!(T::Scheduler::next_dispatch_time(Template::<T>::schedule_id(&caller)).is_ok());
+		assert_eq!(T::Currency::reserved_balance(&caller), T::ScheduleDeposit::get());
+	}
+
+	cancel_scheduled {
+		let caller = funded_caller::<T>();
+		Template::<T>::schedule_set(RawOrigin::Signed(caller.clone()).into(), 100, 10u32.into())?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(T::Scheduler::next_dispatch_time(Template::<T>::schedule_id(&caller)).is_err());
+		assert_eq!(T::Currency::reserved_balance(&caller), 0u32.into());
+	}
+
+	apply_scheduled {
+		fill_history::<T>(0u32.into());
+		let caller = funded_caller::<T>();
+		Template::<T>::schedule_set(RawOrigin::Signed(caller.clone()).into(), 100, 10u32.into())?;
+		let when = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+		frame_system::Pallet::<T>::set_block_number(when);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(SomethingOf::<T>::get(&caller), Some(100));
+		assert!(Scheduled::<T>::get(&caller).is_none());
+	}
+
+	on_initialize {
+		let p in 0 .. T::MaxHistory::get();
+
+		// `p` entries written at genesis have expired, the rest were written after them.
+		let retention = T::HistoryRetention::get();
+		fill_history::<T>(retention);
+		History::<T>::mutate(|history| {
+			history.iter_mut().take(p as usize).for_each(|entry| entry.block = 0u32.into())
+		});
+		let now = retention.saturating_add(1u32.into());
+	}: {
+		Template::<T>::on_initialize(now);
+	}
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(History::<T>::get().len() as u32, T::MaxHistory::get() - p);
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
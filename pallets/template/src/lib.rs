@@ -5,6 +5,8 @@
 /// <https://docs.substrate.io/v3/runtime/frame>
 pub use pallet::*;
 
+use sp_core::crypto::KeyTypeId;
+
 #[cfg(test)]
 mod mock;
 
@@ -14,20 +16,166 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
+/// The log target of this pallet.
+const LOG_TARGET: &str = "runtime::template";
+
+/// The key type of the keys the offchain worker signs its transactions with.
+///
+/// The node's keystore must hold a key of this type for the offchain worker to submit anything. A
+/// key can be added with the `author_insertKey` RPC. Unsigned values are only accepted from the
+/// keys of `Config::OffchainAuthorities`.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"tmpl");
+
+/// The crypto of the keys the offchain worker signs its transactions with.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Signs the offchain worker's transactions with an sr25519 key of type `KEY_TYPE`.
+	pub struct TemplateAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TemplateAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use super::{WeightInfo, LOG_TARGET};
+	use frame_support::{
+		pallet_prelude::*,
+		storage::with_storage_layer,
+		traits::{
+			schedule::{DispatchTime, Named as ScheduleNamed, LOWEST_PRIORITY},
+			Currency, ReservableCurrency, SortedMembers,
+		},
+	};
+	use frame_system::{
+		offchain::{
+			AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
+			SignedPayload, Signer, SigningTypes,
+		},
+		pallet_prelude::*,
+	};
+	use sp_runtime::{
+		traits::{IdentifyAccount, Saturating, StaticLookup, Zero},
+		SaturatedConversion,
+	};
+	use sp_std::vec::Vec;
+
+	/// The balance type of the currency in which storage deposits are held.
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The prefix of the names of the updates scheduled by `schedule_set`.
+	const SCHEDULE_ID_PREFIX: &[u8] = b"template/set";
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
+	/// A write to an account's value, as recorded in `History`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct HistoryEntry<AccountId, BlockNumber> {
+		/// The account whose value was written.
+		pub who: AccountId,
+		/// The value that was written.
+		pub value: u32,
+		/// The block in which the value was written.
+		pub block: BlockNumber,
+	}
+
+	/// An update of an account's value scheduled by `schedule_set`, as recorded in `Scheduled`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct ScheduledUpdate<BlockNumber, Balance> {
+		/// The value the account's value is set to.
+		pub value: u32,
+		/// The block the update is scheduled in.
+		pub when: BlockNumber,
+		/// The deposit reserved from the account until the update is dispatched or cancelled.
+		pub deposit: Balance,
+	}
+
+	/// A value computed by the offchain worker, submitted in `submit_value_unsigned` along with
+	/// a signature of it by `public`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ValuePayload<Public, BlockNumber> {
+		/// The block in which the offchain worker computed the value.
+		pub block_number: BlockNumber,
+		/// The computed value.
+		pub value: u32,
+		/// The key that signed the payload. The value is stored for its account.
+		pub public: Public,
+	}
+
+	impl<T: SigningTypes> SignedPayload<T> for ValuePayload<T::Public, T::BlockNumber> {
+		fn public(&self) -> T::Public {
+			self.public.clone()
+		}
+	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The currency in which storage deposits are held.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The deposit reserved from an account while it holds a value in `SomethingOf`.
+		#[pallet::constant]
+		type StorageDeposit: Get<BalanceOf<Self>>;
+		/// The origin that may overwrite any account's value and freeze writes.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+		/// The maximum number of entries kept in `History`. The oldest entry is dropped to make
+		/// room for a new one.
+		#[pallet::constant]
+		type MaxHistory: Get<u32>;
+		/// The number of blocks an entry is kept in `History` before it is pruned.
+		#[pallet::constant]
+		type HistoryRetention: Get<Self::BlockNumber>;
+		/// The keys the offchain worker signs its transactions with.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// The accounts whose keys may sign the payloads of unsigned values. Values signed by any
+		/// other key are rejected, since unsigned transactions pay no fees.
+		type OffchainAuthorities: SortedMembers<Self::AccountId>;
+		/// The number of blocks between two values submitted by the offchain worker. At most one
+		/// unsigned value is accepted per interval.
+		#[pallet::constant]
+		type OffchainInterval: Get<Self::BlockNumber>;
+		/// The priority of the unsigned transactions submitted by the offchain worker.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// The overarching call type, into which the updates scheduled by `schedule_set` are
+		/// converted.
+		type ScheduledCall: From<Call<Self>>;
+		/// The overarching origin type of the pallets, in which the scheduled updates are
+		/// dispatched.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+		/// The scheduler that dispatches the updates scheduled by `schedule_set`.
+		type Scheduler: ScheduleNamed<Self::BlockNumber, Self::ScheduledCall, Self::PalletsOrigin>;
+		/// The deposit reserved from an account while it has an update scheduled.
+		#[pallet::constant]
+		type ScheduleDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of updates that may be scheduled in the same block, so that they do
+		/// not crowd out the scheduler's other calls in that block.
+		#[pallet::constant]
+		type MaxUpdatesPerBlock: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
@@ -36,7 +184,137 @@ pub mod pallet {
 	#[pallet::getter(fn something)]
 	// Learn more about declaring storage items:
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	//
+	// `Something` holds the aggregate of every account's value in `SomethingOf`. It is a `u64` so
+	// that no account's value can make the aggregate overflow for everyone else. It is `None` while
+	// no account holds a value and the aggregate is zero.
+	pub type Something<T> = StorageValue<_, u64>;
+
+	// Each account's own value. A counted map keeps track of how many accounts hold a value.
+	#[pallet::storage]
+	#[pallet::getter(fn something_of)]
+	pub type SomethingOf<T: Config> =
+		CountedStorageMap<_, Blake2_128Concat, T::AccountId, u32, OptionQuery>;
+
+	// The deposit reserved for each account's entry in `SomethingOf`. Entries written by the admin
+	// origin or seeded at genesis hold no deposit.
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_of)]
+	pub type DepositOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>>;
+
+	// Whether the admin origin has frozen writes through the signed calls.
+	#[pallet::storage]
+	#[pallet::getter(fn is_frozen)]
+	pub type Frozen<T> = StorageValue<_, bool, ValueQuery>;
+
+	// The most recent writes to any account's value, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn history)]
+	pub type History<T: Config> = StorageValue<
+		_,
+		BoundedVec<HistoryEntry<T::AccountId, T::BlockNumber>, T::MaxHistory>,
+		ValueQuery,
+	>;
+
+	// The first block in which the next unsigned value from the offchain worker is accepted.
+	#[pallet::storage]
+	#[pallet::getter(fn next_unsigned_at)]
+	pub type NextUnsignedAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	// The update each account scheduled with `schedule_set`.
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled)]
+	pub type Scheduled<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		ScheduledUpdate<T::BlockNumber, BalanceOf<T>>,
+	>;
+
+	// The number of updates scheduled in each block.
+	#[pallet::storage]
+	pub type ScheduledIn<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, u32, ValueQuery>;
+
+	// The genesis config seeds the pallet's storage when a new chain is built from a chain spec.
+	// https://docs.substrate.io/v3/runtime/chain-specs
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The initial value of `Something`. The seeded per-account values are added on top of it.
+		pub something: Option<u64>,
+		/// The initial value of individual accounts.
+		pub something_of: Vec<(T::AccountId, u32)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { something: None, something_of: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let mut total = self.something;
+			for (who, value) in &self.something_of {
+				assert!(
+					!<SomethingOf<T>>::contains_key(who),
+					"Account has more than one initial value in the genesis config"
+				);
+				total = Some(total.unwrap_or_default().saturating_add(u64::from(*value)));
+				<SomethingOf<T>>::insert(who, value);
+			}
+
+			if let Some(total) = total {
+				<Something<T>>::put(total);
+			}
+		}
+	}
+
+	// Hooks run by the executive at fixed points of every block.
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Prune the entries of `History` that are older than `HistoryRetention`.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let retention = T::HistoryRetention::get();
+			let is_expired = |entry: &HistoryEntry<T::AccountId, T::BlockNumber>| {
+				entry.block.saturating_add(retention) <= now
+			};
+
+			let mut history = <History<T>>::get();
+			// Entries are appended in block order, so the expired ones are at the front.
+			let expired = history.iter().take_while(|entry| is_expired(entry)).count();
+			if expired > 0 {
+				history.retain(|entry| !is_expired(entry));
+				<History<T>>::put(history);
+			}
+
+			T::WeightInfo::on_initialize(expired as u32)
+		}
+
+		/// Compute a value off-chain and submit it back every `OffchainInterval` blocks.
+		///
+		/// The submissions alternate between a signed `do_something` transaction and an unsigned
+		/// `submit_value_unsigned` transaction with a signed payload. Both are signed with a key of
+		/// type `KEY_TYPE` from the node's keystore.
+		fn offchain_worker(block_number: T::BlockNumber) {
+			let interval = T::OffchainInterval::get();
+			if interval.is_zero() || !(block_number % interval).is_zero() {
+				return
+			}
+
+			let value = Self::offchain_value();
+			let result = if ((block_number / interval) % 2u32.into()).is_zero() {
+				Self::submit_signed(value)
+			} else {
+				Self::submit_unsigned_with_signed_payload(block_number, value)
+			};
+
+			if let Err(e) = result {
+				log::error!(target: LOG_TARGET, "Offchain worker failed to submit a value: {}", e);
+			}
+		}
+	}
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
@@ -44,8 +322,21 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored(u32, T::AccountId),
+		/// parameters. [who, old, new]
+		SomethingStored(T::AccountId, Option<u32>, u32),
+		/// An account removed its value from storage. [who, old]
+		SomethingCleared(T::AccountId, u32),
+		/// The admin origin froze writes through the signed calls.
+		WritesFrozen,
+		/// The admin origin unfroze writes through the signed calls.
+		WritesUnfrozen,
+		/// An account scheduled an update of its value. [who, value, when]
+		UpdateScheduled(T::AccountId, u32, T::BlockNumber),
+		/// An account cancelled its scheduled update. [who]
+		ScheduledUpdateCancelled(T::AccountId),
+		/// A value submitted by the offchain worker or scheduled by an account could not be
+		/// stored. [who, value, error]
+		ValueRejected(T::AccountId, u32, DispatchError),
 	}
 
 	// Errors inform users that something went wrong.
@@ -55,48 +346,407 @@ pub mod pallet {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// Writes are frozen by the admin origin.
+		Frozen,
+		/// An update must be scheduled at least one block ahead.
+		NoDelay,
+		/// The account already has a scheduled update.
+		AlreadyScheduled,
+		/// The account has no scheduled update.
+		NotScheduled,
+		/// The block already has `MaxUpdatesPerBlock` updates scheduled.
+		TooManyScheduled,
+		/// The scheduled update is not due yet.
+		NotDue,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
 	// These functions materialize as "extrinsics", which are often compared to transactions.
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	// The weights in `weights.rs` are estimates until they are generated from `benchmarking.rs`.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		/// the caller's storage entry and emits an event. This function must be dispatched by a
+		/// signed extrinsic.
+		#[pallet::weight(T::WeightInfo::do_something())]
 		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
 
-			// Update storage.
-			<Something<T>>::put(something);
-
-			// Emit an event.
-			Self::deposit_event(Event::SomethingStored(something, who));
-			// Return a successful DispatchResultWithPostInfo
-			Ok(())
+			Self::store_own(&who, something)
 		}
 
 		/// An example dispatchable that may throw a custom error.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[pallet::weight(T::WeightInfo::cause_error().max(T::WeightInfo::cause_error_none_value()))]
 		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_frozen(), Error::<T>::Frozen);
 
-			// Read a value from storage.
-			match <Something<T>>::get() {
+			// Read the caller's value from storage.
+			match <SomethingOf<T>>::get(&who) {
 				// Return an error if the value has not been set.
-				None => return Err(Error::<T>::NoneValue.into()),
+				None => Err(Error::<T>::NoneValue.into()),
 				Some(old) => {
 					// Increment the value read from storage; will error in the event of overflow.
 					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
 					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
+					Self::store(&who, new)
+				},
+			}
+		}
+
+		/// Remove the caller's value from storage.
+		#[pallet::weight(T::WeightInfo::clear())]
+		pub fn clear(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_frozen(), Error::<T>::Frozen);
+
+			Self::remove(&who)
+		}
+
+		/// Set the value of any account, even while writes are frozen.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::force_set())]
+		pub fn force_set(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			something: u32,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			Self::store(&who, something)
+		}
+
+		/// Remove the value of any account, even while writes are frozen.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::reset())]
+		pub fn reset(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			Self::remove(&who)
+		}
+
+		/// Reject `do_something`, `cause_error` and `clear` until `unfreeze` is called.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::freeze())]
+		pub fn freeze(origin: OriginFor<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<Frozen<T>>::put(true);
+
+			Self::deposit_event(Event::WritesFrozen);
+			Ok(())
+		}
+
+		/// Accept `do_something`, `cause_error` and `clear` again.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::weight(T::WeightInfo::unfreeze())]
+		pub fn unfreeze(origin: OriginFor<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<Frozen<T>>::kill();
+
+			Self::deposit_event(Event::WritesUnfrozen);
+			Ok(())
+		}
+
+		/// Store a value computed by the offchain worker for the account of the key that signed
+		/// the payload, as if that account had called `do_something`.
+		///
+		/// This function must be dispatched by an unsigned extrinsic. The signature of the payload
+		/// and its signer are checked in `validate_unsigned`.
+		///
+		/// The call succeeds even if the value cannot be stored, emitting `ValueRejected` instead,
+		/// so that the payload is used up either way and cannot be included again for free.
+		#[pallet::weight(T::WeightInfo::submit_value_unsigned())]
+		pub fn submit_value_unsigned(
+			origin: OriginFor<T>,
+			payload: ValuePayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			// Accept the next unsigned value one interval from now.
+			let now = <frame_system::Pallet<T>>::block_number();
+			<NextUnsignedAt<T>>::put(now.saturating_add(T::OffchainInterval::get()));
+
+			let who = payload.public.into_account();
+			if let Err(e) = with_storage_layer(|| Self::store_own(&who, payload.value)) {
+				Self::deposit_event(Event::ValueRejected(who, payload.value, e));
+			}
+			Ok(())
+		}
+
+		/// Schedule the caller's value to be set to `value` `after_blocks` blocks from now.
+		///
+		/// `ScheduleDeposit` is reserved from the caller until the update is dispatched or
+		/// cancelled. An account can have one scheduled update at a time, and a block at most
+		/// `MaxUpdatesPerBlock`. Whether writes are frozen and the deposit of a new entry are only
+		/// checked when the update is dispatched.
+		#[pallet::weight(T::WeightInfo::schedule_set())]
+		pub fn schedule_set(
+			origin: OriginFor<T>,
+			value: u32,
+			after_blocks: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!after_blocks.is_zero(), Error::<T>::NoDelay);
+			ensure!(!<Scheduled<T>>::contains_key(&who), Error::<T>::AlreadyScheduled);
+
+			let when = <frame_system::Pallet<T>>::block_number().saturating_add(after_blocks);
+			let scheduled = <ScheduledIn<T>>::get(when);
+			ensure!(scheduled < T::MaxUpdatesPerBlock::get(), Error::<T>::TooManyScheduled);
+
+			let deposit = T::ScheduleDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			// Scheduling only fails if an update with the same name is already scheduled.
+			T::Scheduler::schedule_named(
+				Self::schedule_id(&who),
+				DispatchTime::At(when),
+				None,
+				LOWEST_PRIORITY,
+				frame_system::RawOrigin::Signed(who.clone()).into(),
+				Call::apply_scheduled {}.into(),
+			)
+			.map_err(|()| Error::<T>::AlreadyScheduled)?;
+			<Scheduled<T>>::insert(&who, ScheduledUpdate { value, when, deposit });
+			<ScheduledIn<T>>::insert(when, scheduled + 1);
+
+			Self::deposit_event(Event::UpdateScheduled(who, value, when));
+			Ok(())
+		}
+
+		/// Cancel the caller's update scheduled by `schedule_set`, returning its deposit.
+		#[pallet::weight(T::WeightInfo::cancel_scheduled())]
+		pub fn cancel_scheduled(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let update = <Scheduled<T>>::get(&who).ok_or(Error::<T>::NotScheduled)?;
+
+			T::Scheduler::cancel_named(Self::schedule_id(&who))
+				.map_err(|()| Error::<T>::NotScheduled)?;
+			Self::unschedule(&who, &update);
+
+			Self::deposit_event(Event::ScheduledUpdateCancelled(who));
+			Ok(())
+		}
+
+		/// Apply the caller's update scheduled by `schedule_set`, as if the caller had called
+		/// `do_something`, and return its deposit.
+		///
+		/// The scheduler dispatches this call on behalf of the caller once the update is due. It
+		/// succeeds even if the value cannot be stored, emitting `ValueRejected` instead, so that
+		/// the deposit is returned either way.
+		#[pallet::weight(T::WeightInfo::apply_scheduled())]
+		pub fn apply_scheduled(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let update = <Scheduled<T>>::get(&who).ok_or(Error::<T>::NotScheduled)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(update.when <= now, Error::<T>::NotDue);
+
+			Self::unschedule(&who, &update);
+			if let Err(e) = with_storage_layer(|| Self::store_own(&who, update.value)) {
+				Self::deposit_event(Event::ValueRejected(who, update.value, e));
+			}
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Accept a `submit_value_unsigned` transaction with a valid signature of its payload by
+		/// one of the `OffchainAuthorities`, computed no earlier than `NextUnsignedAt`, while
+		/// writes are not frozen and the signer can pay the deposit of a new entry.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (payload, signature) = match call {
+				Call::submit_value_unsigned { payload, signature } => (payload, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+			let who = payload.public.clone().into_account();
+			if !T::OffchainAuthorities::contains(&who) {
+				return InvalidTransaction::BadSigner.into()
+			}
+			if Self::is_frozen() {
+				return InvalidTransaction::Call.into()
+			}
+
+			let next_unsigned_at = <NextUnsignedAt<T>>::get();
+			if payload.block_number < next_unsigned_at {
+				return InvalidTransaction::Stale.into()
+			}
+			if payload.block_number > <frame_system::Pallet<T>>::block_number() {
+				return InvalidTransaction::Future.into()
+			}
+			if !<SomethingOf<T>>::contains_key(&who) &&
+				!T::Currency::can_reserve(&who, T::StorageDeposit::get())
+			{
+				return InvalidTransaction::Payment.into()
+			}
+
+			ValidTransaction::with_tag_prefix("TemplateOffchainWorker")
+				.priority(T::UnsignedPriority::get())
+				// Only one unsigned value is accepted per interval.
+				.and_provides(next_unsigned_at)
+				.longevity(T::OffchainInterval::get().saturated_into::<u64>())
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The name under which the update of `who` is scheduled.
+		pub(crate) fn schedule_id(who: &T::AccountId) -> Vec<u8> {
+			(SCHEDULE_ID_PREFIX, who).encode()
+		}
+
+		/// Forget the `update` scheduled by `who` and return its deposit.
+		fn unschedule(who: &T::AccountId, update: &ScheduledUpdate<T::BlockNumber, BalanceOf<T>>) {
+			<Scheduled<T>>::remove(who);
+			<ScheduledIn<T>>::mutate_exists(update.when, |scheduled| {
+				*scheduled = scheduled.and_then(|n| n.checked_sub(1)).filter(|n| !n.is_zero());
+			});
+			T::Currency::unreserve(who, update.deposit);
+		}
+
+		/// Set the value of `who` on their own behalf, holding a deposit for a new entry.
+		///
+		/// Fails with `Frozen` while writes are frozen.
+		fn store_own(who: &T::AccountId, new: u32) -> DispatchResult {
+			ensure!(!Self::is_frozen(), Error::<T>::Frozen);
+
+			// Hold a deposit for as long as the entry takes up storage.
+			if !<SomethingOf<T>>::contains_key(who) {
+				let deposit = T::StorageDeposit::get();
+				T::Currency::reserve(who, deposit)?;
+				<DepositOf<T>>::insert(who, deposit);
+			}
+
+			// Update storage and emit an event.
+			Self::store(who, new)
+		}
+
+		/// Set the value of `who` to `new`, keeping the aggregate in `Something` up to date.
+		fn store(who: &T::AccountId, new: u32) -> DispatchResult {
+			let old = <SomethingOf<T>>::get(who);
+			// A sum of `u32` values does not overflow a `u64` in practice. Saturating rather than
+			// failing makes sure that one account's value never fails another account's write.
+			let total = <Something<T>>::get()
+				.unwrap_or_default()
+				.saturating_sub(old.map(u64::from).unwrap_or_default())
+				.saturating_add(u64::from(new));
+
+			<SomethingOf<T>>::insert(who, new);
+			<Something<T>>::put(total);
+			Self::record(who, new);
+
+			Self::deposit_event(Event::SomethingStored(who.clone(), old, new));
+			Ok(())
+		}
+
+		/// Append a write to `History`, dropping the oldest entry if it is full.
+		fn record(who: &T::AccountId, value: u32) {
+			let entry = HistoryEntry {
+				who: who.clone(),
+				value,
+				block: <frame_system::Pallet<T>>::block_number(),
+			};
+
+			<History<T>>::mutate(|history| {
+				if history.len() >= T::MaxHistory::get() as usize && !history.is_empty() {
+					history.remove(0);
+				}
+				// Only fails if `MaxHistory` is zero, in which case no history is kept.
+				let _ = history.try_push(entry);
+			});
+		}
+
+		/// Remove the value of `who`, keeping the aggregate in `Something` up to date and returning
+		/// the deposit held for it.
+		///
+		/// Fails with `NoneValue` if `who` holds no value.
+		fn remove(who: &T::AccountId) -> DispatchResult {
+			let old = <SomethingOf<T>>::take(who).ok_or(Error::<T>::NoneValue)?;
+			<Something<T>>::mutate(|total| {
+				let rest = total.unwrap_or_default().saturating_sub(u64::from(old));
+				// Clearing the last value leaves no aggregate, as if nothing had been stored.
+				*total = if rest.is_zero() && <SomethingOf<T>>::count() == 0 {
+					None
+				} else {
+					Some(rest)
+				};
+			});
+
+			if let Some(deposit) = <DepositOf<T>>::take(who) {
+				T::Currency::unreserve(who, deposit);
+			}
+
+			Self::deposit_event(Event::SomethingCleared(who.clone(), old));
+			Ok(())
+		}
+
+		/// The value submitted by the offchain worker, derived from the hash of the parent block.
+		fn offchain_value() -> u32 {
+			let parent_hash = <frame_system::Pallet<T>>::parent_hash();
+			let mut bytes = [0u8; 4];
+			bytes.copy_from_slice(&parent_hash.as_ref()[..4]);
+			// Keep the values small so that they hardly ever overflow the aggregate.
+			u32::from_le_bytes(bytes) % 1_000
+		}
+
+		/// Submit `value` in a signed `do_something` transaction from any local account.
+		fn submit_signed(value: u32) -> Result<(), &'static str> {
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			if !signer.can_sign() {
+				return Err(
+					"No local accounts available. Add a key with the `author_insertKey` RPC.",
+				)
+			}
+
+			match signer.send_signed_transaction(|_| Call::do_something { something: value }) {
+				Some((account, Ok(()))) => {
+					log::info!(target: LOG_TARGET, "[{:?}] Submitted value {}", account.id, value);
 					Ok(())
 				},
+				Some((_, Err(()))) => Err("Failed to submit the signed transaction"),
+				None => Err("No local account signed the transaction"),
 			}
 		}
+
+		/// Submit `value` in an unsigned `submit_value_unsigned` transaction, with a payload signed
+		/// by any local account.
+		fn submit_unsigned_with_signed_payload(
+			block_number: T::BlockNumber,
+			value: u32,
+		) -> Result<(), &'static str> {
+			if block_number < <NextUnsignedAt<T>>::get() {
+				return Err("Too early to submit an unsigned transaction")
+			}
+
+			let (_, result) = Signer::<T, T::AuthorityId>::any_account()
+				.send_unsigned_transaction(
+					|account| ValuePayload { block_number, value, public: account.public.clone() },
+					|payload, signature| Call::submit_value_unsigned { payload, signature },
+				)
+				.ok_or("No local accounts available. Add a key with the `author_insertKey` RPC.")?;
+
+			result.map_err(|()| "Failed to submit the unsigned transaction")
+		}
 	}
 }
@@ -0,0 +1,274 @@
+//! Storage migrations for the template pallet.
+//!
+//! Every change to the layout of the pallet's storage bumps `STORAGE_VERSION` in `lib.rs` and adds
+//! a module here with an `OnRuntimeUpgrade` implementation that brings the storage of a live chain
+//! up to date. The runtime registers the migrations it needs in its `Migrations` tuple.
+
+use super::*;
+use frame_support::{
+	storage::{storage_prefix, unhashed, KeyPrefixIterator},
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+/// The key prefix of the rate limit counts that the pallet kept in storage versions 2 and 3, before
+/// they moved to `pallet_rate_limit`.
+fn rate_limit_count_prefix<T: Config>() -> [u8; 32] {
+	storage_prefix(Pallet::<T>::name().as_bytes(), b"RateLimitCount")
+}
+
+/// Remove the rate limit counts from the pallet's storage, and return the weight of doing so.
+fn remove_rate_limit_counts<T: Config>() -> (u32, Weight) {
+	let counts = unhashed::clear_prefix(&rate_limit_count_prefix::<T>(), None, None);
+	(counts.unique, T::DbWeight::get().reads_writes(counts.loops.into(), counts.unique.into()))
+}
+
+/// Whether any rate limit count is left in the pallet's storage.
+#[cfg(feature = "try-runtime")]
+fn has_rate_limit_counts<T: Config>() -> bool {
+	let prefix = rate_limit_count_prefix::<T>().to_vec();
+	KeyPrefixIterator::new(prefix.clone(), prefix, |_| Ok(())).next().is_some()
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// Migrates the pallet's storage from version 0 to version 1.
+	///
+	/// Version 1 introduced the per-account values in `SomethingOf`. The layout of `Something` is
+	/// unchanged and its value on a live chain becomes the base of the aggregate, so only the
+	/// storage version needs to be recorded.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+			if on_chain_version == 0 {
+				StorageVersion::new(1).put::<Pallet<T>>();
+				log::info!(target: LOG_TARGET, "migrated storage to version 1");
+				T::DbWeight::get().reads_writes(1, 1)
+			} else {
+				log::warn!(
+					target: LOG_TARGET,
+					"skipping migration to version 1, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 0,
+				"the storage version must be 0 before migrating to version 1"
+			);
+			Self::set_temp_storage(something::<T>(), "something");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"the storage version must be 1 after the migration"
+			);
+			let something: Option<u32> = Self::get_temp_storage("something")
+				.ok_or("the value of `Something` was not recorded before the migration")?;
+			frame_support::ensure!(
+				something::<T>() == something,
+				"the migration must not change the value of `Something`"
+			);
+			Ok(())
+		}
+	}
+
+	/// The value of `Something` while it was a `u32`, up to version 1.
+	pub(crate) fn something<T: Config>() -> Option<u32> {
+		frame_support::storage::unhashed::get(&Something::<T>::hashed_key())
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// Migrates the pallet's storage from version 1 to version 2.
+	///
+	/// Version 2 widened the aggregate in `Something` from a `u32` to a `u64`, so that one
+	/// account's value can no longer make every other account's write overflow it.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+			if on_chain_version == 1 {
+				if Something::<T>::translate::<u32, _>(|total| total.map(u64::from)).is_err() {
+					log::error!(target: LOG_TARGET, "the value of `Something` is not a `u32`");
+				}
+				StorageVersion::new(2).put::<Pallet<T>>();
+				log::info!(target: LOG_TARGET, "migrated storage to version 2");
+				T::DbWeight::get().reads_writes(2, 2)
+			} else {
+				log::warn!(
+					target: LOG_TARGET,
+					"skipping migration to version 2, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"the storage version must be 1 before migrating to version 2"
+			);
+			Self::set_temp_storage(v1::something::<T>(), "something");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 2,
+				"the storage version must be 2 after the migration"
+			);
+			let something: Option<u32> = Self::get_temp_storage("something")
+				.ok_or("the value of `Something` was not recorded before the migration")?;
+			frame_support::ensure!(
+				Something::<T>::get() == something.map(u64::from),
+				"the migration must keep the value of `Something`"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Migrates the pallet's storage from version 2 to version 3.
+	///
+	/// Version 3 keyed the counts in `RateLimitCount` by the block in which their period ends, so
+	/// that they can be removed when it does. The counts in the old layout are removed, which
+	/// starts every account's rate limit periods over.
+	///
+	/// The counts have since moved to `pallet_rate_limit`, so their storage is removed by its raw
+	/// key prefix.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+			if on_chain_version == 2 {
+				let (counts, weight) = remove_rate_limit_counts::<T>();
+				StorageVersion::new(3).put::<Pallet<T>>();
+				log::info!(
+					target: LOG_TARGET,
+					"migrated storage to version 3, removed {} rate limit counts",
+					counts
+				);
+				weight.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				log::warn!(
+					target: LOG_TARGET,
+					"skipping migration to version 3, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 2,
+				"the storage version must be 2 before migrating to version 3"
+			);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 3,
+				"the storage version must be 3 after the migration"
+			);
+			frame_support::ensure!(
+				!has_rate_limit_counts::<T>(),
+				"the migration must remove every rate limit count"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// Migrates the pallet's storage from version 3 to version 4.
+	///
+	/// Version 4 moved the rate limit counts to `pallet_rate_limit`, which keeps them in its own
+	/// storage. The counts left in this pallet's `RateLimitCount` are removed, which starts every
+	/// account's rate limit periods over.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+			if on_chain_version == 3 {
+				let (counts, weight) = remove_rate_limit_counts::<T>();
+				StorageVersion::new(4).put::<Pallet<T>>();
+				log::info!(
+					target: LOG_TARGET,
+					"migrated storage to version 4, removed {} rate limit counts",
+					counts
+				);
+				weight.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				log::warn!(
+					target: LOG_TARGET,
+					"skipping migration to version 4, on-chain storage version is {:?}",
+					on_chain_version
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 3,
+				"the storage version must be 3 before migrating to version 4"
+			);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 4,
+				"the storage version must be 4 after the migration"
+			);
+			frame_support::ensure!(
+				!has_rate_limit_counts::<T>(),
+				"the migration must remove every rate limit count"
+			);
+			Ok(())
+		}
+	}
+}
@@ -1,15 +1,27 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
-use frame_system as system;
-use sp_core::H256;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, EqualPrivilegeOnly, SortedMembers};
+use frame_system::{self as system, offchain::AppCrypto};
+use parking_lot::RwLock;
+use sp_core::{
+	offchain::{
+		testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+		OffchainWorkerExt, TransactionPoolExt,
+	},
+	H256,
+};
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	BuildStorage,
 };
+use std::sync::Arc;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+/// The extrinsics submitted by the offchain worker.
+pub type Extrinsic = TestXt<Call, ()>;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -18,7 +30,10 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		TemplateModule: pallet_template::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
 	}
 );
 
@@ -40,7 +55,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -49,11 +64,150 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_preimage::Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = frame_system::EnsureRoot<u64>;
+	type MaxSize = ConstU32<1024>;
+	type BaseDeposit = ConstU64<2>;
+	type ByteDeposit = ConstU64<1>;
+}
+
+impl pallet_scheduler::Config for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = ConstU64<1_000_000_000_000>;
+	type ScheduleOrigin = frame_system::EnsureRoot<u64>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type WeightInfo = ();
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = ();
+}
+
+/// The deposit held for each account's entry.
+pub const DEPOSIT: u64 = 10;
+
+/// The deposit held while an account has an update scheduled.
+pub const SCHEDULE_DEPOSIT: u64 = 5;
+
+/// The maximum number of updates scheduled in the same block.
+pub const MAX_UPDATES_PER_BLOCK: u32 = 2;
+
+/// The balance of the accounts endowed at genesis.
+pub const ENDOWMENT: u64 = 100;
+
+/// Signs with the `UintAuthorityId` keys set by `UintAuthorityId::set_all_keys`. The account of a
+/// key is its number.
+pub struct TestAuthId;
+
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+/// The number of blocks between two values submitted by the offchain worker.
+pub const OFFCHAIN_INTERVAL: u64 = 4;
+
+/// The accounts whose keys may sign unsigned values: the offchain worker's key 1, and the unfunded
+/// account 4.
+pub struct TestOffchainAuthorities;
+
+impl SortedMembers<u64> for TestOffchainAuthorities {
+	fn sorted_members() -> Vec<u64> {
+		vec![1, 4]
+	}
+}
+
 impl pallet_template::Config for Test {
 	type Event = Event;
+	type Currency = Balances;
+	type StorageDeposit = ConstU64<DEPOSIT>;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type MaxHistory = ConstU32<3>;
+	type HistoryRetention = ConstU64<10>;
+	type AuthorityId = TestAuthId;
+	type OffchainAuthorities = TestOffchainAuthorities;
+	type OffchainInterval = ConstU64<OFFCHAIN_INTERVAL>;
+	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+	type ScheduledCall = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type ScheduleDeposit = ConstU64<SCHEDULE_DEPOSIT>;
+	type MaxUpdatesPerBlock = ConstU32<MAX_UPDATES_PER_BLOCK>;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	new_test_ext_with(Default::default())
+}
+
+// Build genesis storage with the given template pallet genesis config. Accounts 1 to 3 are endowed.
+pub fn new_test_ext_with(
+	template_module: pallet_template::GenesisConfig<Test>,
+) -> sp_io::TestExternalities {
+	GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig { balances: (1..=3).map(|who| (who, ENDOWMENT)).collect() },
+		template_module,
+	}
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+// Build genesis storage that the offchain worker can submit transactions from, with key 1 in its
+// keystore. Returns the state of the transaction pool the offchain worker submits to.
+pub fn new_offchain_test_ext() -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>) {
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	UintAuthorityId::set_all_keys(vec![1u64]);
+	(ext, pool_state)
 }
@@ -1,5 +1,20 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{migrations, mock::*, Error, Event as TemplateEvent, HistoryEntry, ValuePayload};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchError,
+	storage::{storage_prefix, unhashed},
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+};
+use proptest::{collection::vec, prelude::*};
+use sp_core::H256;
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::ValidateUnsigned,
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+	RuntimeAppPublic,
+};
+use std::collections::BTreeMap;
 
 #[test]
 fn it_works_for_default_value() {
@@ -7,6 +22,7 @@ fn it_works_for_default_value() {
 		// Dispatch a signed extrinsic.
 		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
 		// Read pallet storage and assert an expected result.
+		assert_eq!(TemplateModule::something_of(1), Some(42));
 		assert_eq!(TemplateModule::something(), Some(42));
 	});
 }
@@ -18,3 +34,758 @@ fn correct_error_for_none_value() {
 		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
 	});
 }
+
+#[test]
+fn values_are_kept_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 20));
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 15));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(2)));
+
+		assert_eq!(TemplateModule::something_of(1), Some(15));
+		assert_eq!(TemplateModule::something_of(2), Some(21));
+		// The global value is the aggregate of every account's value.
+		assert_eq!(TemplateModule::something(), Some(36));
+		assert_eq!(crate::SomethingOf::<Test>::count(), 2);
+
+		// Another account's value is not visible to `cause_error`.
+		assert_noop!(TemplateModule::cause_error(Origin::signed(3)), Error::<Test>::NoneValue);
+	});
+}
+
+#[test]
+fn events_carry_old_and_new_value() {
+	new_test_ext().execute_with(|| {
+		// Events are not emitted on the genesis block.
+		System::set_block_number(1);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+		System::assert_last_event(TemplateEvent::SomethingStored(1, None, 42).into());
+
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		System::assert_last_event(TemplateEvent::SomethingStored(1, Some(42), 43).into());
+
+		assert_ok!(TemplateModule::clear(Origin::signed(1)));
+		System::assert_last_event(TemplateEvent::SomethingCleared(1, 43).into());
+	});
+}
+
+#[test]
+fn clear_removes_the_callers_entry() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::clear(Origin::signed(1)), Error::<Test>::NoneValue);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 20));
+		assert_ok!(TemplateModule::clear(Origin::signed(1)));
+
+		assert_eq!(TemplateModule::something_of(1), None);
+		assert_eq!(TemplateModule::something_of(2), Some(20));
+		assert_eq!(TemplateModule::something(), Some(20));
+		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+
+		// Clearing the last value leaves no aggregate, like an empty genesis.
+		assert_ok!(TemplateModule::clear(Origin::signed(2)));
+		assert_eq!(TemplateModule::something(), None);
+	});
+}
+
+#[test]
+fn correct_error_for_overflow() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), u32::MAX));
+		assert_noop!(
+			TemplateModule::cause_error(Origin::signed(1)),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+#[test]
+fn one_accounts_value_does_not_fail_anothers_write() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), u32::MAX));
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 1));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(2)));
+		assert_eq!(TemplateModule::something(), Some(u64::from(u32::MAX) + 2));
+
+		// Replacing an existing value only counts the difference.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 1));
+		assert_eq!(TemplateModule::something(), Some(3));
+	});
+}
+
+#[test]
+fn genesis_config_seeds_storage() {
+	let config = crate::GenesisConfig { something: Some(5), something_of: vec![(1, 10), (2, 20)] };
+	new_test_ext_with(config).execute_with(|| {
+		assert_eq!(TemplateModule::something_of(1), Some(10));
+		assert_eq!(TemplateModule::something_of(2), Some(20));
+		assert_eq!(TemplateModule::something(), Some(35));
+		assert_eq!(crate::SomethingOf::<Test>::count(), 2);
+
+		// Seeded values behave like any other.
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something(), Some(36));
+	});
+}
+
+#[test]
+fn default_genesis_config_leaves_storage_empty() {
+	new_test_ext_with(Default::default()).execute_with(|| {
+		assert_eq!(TemplateModule::something(), None);
+		assert_eq!(crate::SomethingOf::<Test>::count(), 0);
+	});
+}
+
+/// Store `total` in `Something` as a `u32`, as the pallet did up to storage version 1.
+fn put_v1_something(total: u32) {
+	frame_support::storage::unhashed::put(&crate::Something::<Test>::hashed_key(), &total);
+}
+
+#[test]
+fn migration_to_v1_keeps_the_global_value() {
+	new_test_ext().execute_with(|| {
+		// A chain running the pallet before per-account values were introduced.
+		StorageVersion::new(0).put::<TemplateModule>();
+		put_v1_something(42);
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(TemplateModule::on_chain_storage_version(), 2);
+		assert_eq!(TemplateModule::something(), Some(42));
+
+		// The existing value is the base of the aggregate.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 8));
+		assert_eq!(TemplateModule::something(), Some(50));
+	});
+}
+
+#[test]
+fn migration_to_v1_only_runs_once() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<TemplateModule>();
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn migration_to_v2_widens_the_aggregate() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<TemplateModule>();
+		put_v1_something(u32::MAX);
+
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 2);
+		assert_eq!(TemplateModule::something(), Some(u64::from(u32::MAX)));
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 1));
+		assert_eq!(TemplateModule::something(), Some(u64::from(u32::MAX) + 1));
+
+		// Running the migration again does nothing.
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(TemplateModule::something(), Some(u64::from(u32::MAX) + 1));
+	});
+}
+
+// Put rate limit counts into the storage the pallet kept them in before they moved to
+// `pallet_rate_limit`, and return the key prefix they are under.
+fn put_rate_limit_counts() -> [u8; 32] {
+	let prefix = storage_prefix(b"TemplateModule", b"RateLimitCount");
+	for (account, count) in [(1u64, 1u32), (2, 2)] {
+		let key = [&prefix[..], &(account, ()).encode()].concat();
+		unhashed::put(&key, &(4u64, count));
+	}
+	prefix
+}
+
+// Whether any key is stored under `prefix`.
+fn has_keys(prefix: &[u8]) -> bool {
+	sp_io::storage::next_key(prefix).map_or(false, |key| key.starts_with(prefix))
+}
+
+#[test]
+fn migration_to_v3_removes_the_rate_limit_counts() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<TemplateModule>();
+		let prefix = put_rate_limit_counts();
+
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 3);
+		assert!(!has_keys(&prefix));
+	});
+}
+
+#[test]
+fn migration_to_v4_removes_the_rate_limit_counts_left() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(3).put::<TemplateModule>();
+		let prefix = put_rate_limit_counts();
+
+		migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			TemplateModule::on_chain_storage_version(),
+			TemplateModule::current_storage_version()
+		);
+		assert!(!has_keys(&prefix));
+
+		// Running the migration again does nothing.
+		let prefix = put_rate_limit_counts();
+		migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+		assert!(has_keys(&prefix));
+	});
+}
+
+#[test]
+fn admin_calls_require_the_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::force_set(Origin::signed(1), 1, 42), DispatchError::BadOrigin);
+		assert_noop!(TemplateModule::reset(Origin::signed(1), 1), DispatchError::BadOrigin);
+		assert_noop!(TemplateModule::freeze(Origin::signed(1)), DispatchError::BadOrigin);
+		assert_noop!(TemplateModule::unfreeze(Origin::signed(1)), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn admin_can_force_set_and_reset_any_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 10));
+		assert_ok!(TemplateModule::force_set(Origin::root(), 1, 42));
+		System::assert_last_event(TemplateEvent::SomethingStored(1, None, 42).into());
+		assert_eq!(TemplateModule::something_of(1), Some(42));
+		assert_eq!(TemplateModule::something(), Some(52));
+
+		assert_ok!(TemplateModule::reset(Origin::root(), 1));
+		System::assert_last_event(TemplateEvent::SomethingCleared(1, 42).into());
+		assert_eq!(TemplateModule::something_of(1), None);
+		assert_eq!(TemplateModule::something(), Some(10));
+
+		assert_noop!(TemplateModule::reset(Origin::root(), 1), Error::<Test>::NoneValue);
+	});
+}
+
+#[test]
+fn signed_calls_are_rejected_while_frozen() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+
+		assert_ok!(TemplateModule::freeze(Origin::root()));
+		System::assert_last_event(TemplateEvent::WritesFrozen.into());
+		assert!(TemplateModule::is_frozen());
+
+		assert_noop!(TemplateModule::do_something(Origin::signed(1), 20), Error::<Test>::Frozen);
+		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::Frozen);
+		assert_noop!(TemplateModule::clear(Origin::signed(1)), Error::<Test>::Frozen);
+
+		// The admin origin can still write.
+		assert_ok!(TemplateModule::force_set(Origin::root(), 1, 20));
+
+		assert_ok!(TemplateModule::unfreeze(Origin::root()));
+		System::assert_last_event(TemplateEvent::WritesUnfrozen.into());
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(21));
+	});
+}
+
+#[test]
+fn writes_are_recorded_in_history() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_ok!(TemplateModule::force_set(Origin::root(), 2, 20));
+		// Failed writes and removals are not recorded.
+		assert_noop!(TemplateModule::cause_error(Origin::signed(3)), Error::<Test>::NoneValue);
+		assert_ok!(TemplateModule::clear(Origin::signed(2)));
+
+		assert_eq!(
+			TemplateModule::history().into_inner(),
+			vec![
+				HistoryEntry { who: 1, value: 10, block: 1 },
+				HistoryEntry { who: 1, value: 11, block: 2 },
+				HistoryEntry { who: 2, value: 20, block: 2 },
+			]
+		);
+	});
+}
+
+#[test]
+fn history_drops_the_oldest_entry_when_full() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for value in 1..=5 {
+			assert_ok!(TemplateModule::do_something(Origin::signed(1), value));
+		}
+
+		let values: Vec<u32> = TemplateModule::history().iter().map(|entry| entry.value).collect();
+		assert_eq!(values, vec![3, 4, 5]);
+	});
+}
+
+#[test]
+fn history_is_pruned_by_age() {
+	new_test_ext().execute_with(|| {
+		for block in [1, 5, 8] {
+			System::set_block_number(block);
+			assert_ok!(TemplateModule::do_something(Origin::signed(1), block as u32));
+		}
+
+		// Nothing is older than ten blocks yet.
+		TemplateModule::on_initialize(10);
+		assert_eq!(TemplateModule::history().len(), 3);
+
+		TemplateModule::on_initialize(15);
+		let blocks: Vec<u64> = TemplateModule::history().iter().map(|entry| entry.block).collect();
+		assert_eq!(blocks, vec![8]);
+
+		TemplateModule::on_initialize(18);
+		assert!(TemplateModule::history().is_empty());
+	});
+}
+
+#[test]
+fn creating_an_entry_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_eq!(Balances::reserved_balance(1), DEPOSIT);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT - DEPOSIT);
+		assert_eq!(TemplateModule::deposit_of(1), Some(DEPOSIT));
+
+		// Updating an existing entry does not take another deposit.
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 20));
+		assert_ok!(TemplateModule::cause_error(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), DEPOSIT);
+	});
+}
+
+#[test]
+fn clearing_an_entry_unreserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 10));
+		assert_ok!(TemplateModule::clear(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT);
+		assert_eq!(TemplateModule::deposit_of(1), None);
+
+		// The admin origin returns the deposit too.
+		assert_ok!(TemplateModule::do_something(Origin::signed(2), 10));
+		assert_ok!(TemplateModule::reset(Origin::root(), 2));
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn entries_without_a_deposit_return_nothing() {
+	new_test_ext_with(crate::GenesisConfig { something: None, something_of: vec![(1, 10)] })
+		.execute_with(|| {
+			// Neither seeded nor forced entries hold a deposit.
+			assert_ok!(TemplateModule::force_set(Origin::root(), 2, 20));
+			assert_eq!(TemplateModule::deposit_of(1), None);
+			assert_eq!(TemplateModule::deposit_of(2), None);
+
+			assert_ok!(TemplateModule::clear(Origin::signed(1)));
+			assert_ok!(TemplateModule::clear(Origin::signed(2)));
+			assert_eq!(Balances::free_balance(1), ENDOWMENT);
+			assert_eq!(Balances::free_balance(2), ENDOWMENT);
+		});
+}
+
+#[test]
+fn creating_an_entry_fails_without_funds_for_the_deposit() {
+	new_test_ext().execute_with(|| {
+		// Account 4 has no balance.
+		assert_noop!(
+			TemplateModule::do_something(Origin::signed(4), 10),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(TemplateModule::something_of(4), None);
+	});
+}
+
+// The value the offchain worker computes from a parent hash of `1` bytes: 0x01010101 % 1000.
+const OFFCHAIN_VALUE: u32 = 9;
+
+fn run_offchain_worker(block: u64) {
+	System::initialize(&block, &H256::repeat_byte(1), &Default::default());
+	TemplateModule::offchain_worker(block);
+}
+
+fn validate(
+	payload: &ValuePayload<UintAuthorityId, u64>,
+	signature: &TestSignature,
+) -> TransactionValidity {
+	let call = crate::Call::submit_value_unsigned {
+		payload: payload.clone(),
+		signature: signature.clone(),
+	};
+	TemplateModule::validate_unsigned(TransactionSource::External, &call)
+}
+
+#[test]
+fn offchain_worker_submits_a_value_every_interval() {
+	let (mut ext, pool_state) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		for block in 1..OFFCHAIN_INTERVAL {
+			run_offchain_worker(block);
+		}
+		assert!(pool_state.read().transactions.is_empty());
+
+		run_offchain_worker(OFFCHAIN_INTERVAL);
+		run_offchain_worker(2 * OFFCHAIN_INTERVAL);
+		assert_eq!(pool_state.read().transactions.len(), 2);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_a_signed_transaction() {
+	let (mut ext, pool_state) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		// Every other interval the value is submitted in a signed transaction.
+		run_offchain_worker(2 * OFFCHAIN_INTERVAL);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, Some((0, ())));
+		assert_eq!(
+			tx.call,
+			Call::TemplateModule(crate::Call::do_something { something: OFFCHAIN_VALUE })
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_an_unsigned_transaction_with_signed_payload() {
+	let (mut ext, pool_state) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		// The other intervals the value is submitted in an unsigned transaction.
+		run_offchain_worker(OFFCHAIN_INTERVAL);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		let (payload, signature) = match tx.call {
+			Call::TemplateModule(crate::Call::submit_value_unsigned { payload, signature }) =>
+				(payload, signature),
+			call => panic!("unexpected call: {:?}", call),
+		};
+		assert_eq!(
+			payload,
+			ValuePayload {
+				block_number: OFFCHAIN_INTERVAL,
+				value: OFFCHAIN_VALUE,
+				public: UintAuthorityId(1)
+			}
+		);
+
+		// The value is stored for the account of the key that signed the payload.
+		assert_ok!(validate(&payload, &signature));
+		assert_ok!(TemplateModule::submit_value_unsigned(Origin::none(), payload, signature));
+		assert_eq!(TemplateModule::something_of(1), Some(OFFCHAIN_VALUE));
+		assert_eq!(TemplateModule::deposit_of(1), Some(DEPOSIT));
+		assert_eq!(TemplateModule::next_unsigned_at(), 2 * OFFCHAIN_INTERVAL);
+	});
+}
+
+#[test]
+fn unsigned_values_are_validated() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(OFFCHAIN_INTERVAL);
+		let payload =
+			ValuePayload { block_number: OFFCHAIN_INTERVAL, value: 7, public: UintAuthorityId(1) };
+		let sign = |payload: &ValuePayload<UintAuthorityId, u64>, key: u64| {
+			UintAuthorityId(key).sign(&payload.encode()).unwrap()
+		};
+		assert_ok!(validate(&payload, &sign(&payload, 1)));
+
+		// The payload must be signed by its own key.
+		assert_eq!(validate(&payload, &sign(&payload, 2)), InvalidTransaction::BadProof.into());
+
+		// The key must be one of the offchain authorities.
+		let unauthorized = ValuePayload { public: UintAuthorityId(2), ..payload.clone() };
+		assert_eq!(
+			validate(&unauthorized, &sign(&unauthorized, 2)),
+			InvalidTransaction::BadSigner.into()
+		);
+
+		// The signer must be able to pay the deposit of a new entry.
+		let unfunded = ValuePayload { public: UintAuthorityId(UNFUNDED), ..payload.clone() };
+		assert_eq!(
+			validate(&unfunded, &sign(&unfunded, UNFUNDED)),
+			InvalidTransaction::Payment.into()
+		);
+
+		// Values computed in a future block are rejected.
+		let future = ValuePayload { block_number: OFFCHAIN_INTERVAL + 1, ..payload.clone() };
+		assert_eq!(validate(&future, &sign(&future, 1)), InvalidTransaction::Future.into());
+
+		// Values are rejected while writes are frozen.
+		assert_ok!(TemplateModule::freeze(Origin::root()));
+		assert_eq!(validate(&payload, &sign(&payload, 1)), InvalidTransaction::Call.into());
+		assert_ok!(TemplateModule::unfreeze(Origin::root()));
+
+		// Only one value is accepted per interval.
+		assert_ok!(TemplateModule::submit_value_unsigned(
+			Origin::none(),
+			payload.clone(),
+			sign(&payload, 1)
+		));
+		assert_eq!(validate(&payload, &sign(&payload, 1)), InvalidTransaction::Stale.into());
+	});
+}
+
+#[test]
+fn rejected_unsigned_values_still_use_up_the_interval() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(OFFCHAIN_INTERVAL);
+		let payload = ValuePayload {
+			block_number: OFFCHAIN_INTERVAL,
+			value: 7,
+			public: UintAuthorityId(UNFUNDED),
+		};
+		let signature = UintAuthorityId(UNFUNDED).sign(&payload.encode()).unwrap();
+
+		// The value cannot be stored without the deposit, but the call succeeds.
+		assert_ok!(TemplateModule::submit_value_unsigned(
+			Origin::none(),
+			payload.clone(),
+			signature.clone()
+		));
+		System::assert_last_event(
+			TemplateEvent::ValueRejected(
+				UNFUNDED,
+				7,
+				pallet_balances::Error::<Test>::InsufficientBalance.into(),
+			)
+			.into(),
+		);
+		assert_eq!(TemplateModule::something_of(UNFUNDED), None);
+
+		// So the payload cannot be included again.
+		assert_eq!(TemplateModule::next_unsigned_at(), 2 * OFFCHAIN_INTERVAL);
+		assert_eq!(validate(&payload, &signature), InvalidTransaction::Stale.into());
+	});
+}
+
+#[test]
+fn scheduled_updates_are_dispatched_for_the_caller() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::schedule_set(Origin::signed(1), 42, 5));
+		System::assert_last_event(TemplateEvent::UpdateScheduled(1, 42, 6).into());
+		assert_eq!(Balances::reserved_balance(1), SCHEDULE_DEPOSIT);
+		assert_eq!(TemplateModule::scheduled(1).map(|update| update.when), Some(6));
+
+		Scheduler::on_initialize(5);
+		assert_eq!(TemplateModule::something_of(1), None);
+
+		// The update is dispatched as if the caller had called `do_something`, and its deposit
+		// is returned.
+		System::set_block_number(6);
+		Scheduler::on_initialize(6);
+		assert_eq!(TemplateModule::something_of(1), Some(42));
+		assert_eq!(TemplateModule::deposit_of(1), Some(DEPOSIT));
+		assert_eq!(Balances::reserved_balance(1), DEPOSIT);
+		assert_eq!(TemplateModule::scheduled(1), None);
+	});
+}
+
+#[test]
+fn scheduled_updates_return_their_deposit_when_rejected() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::schedule_set(Origin::signed(1), 42, 5));
+		assert_ok!(TemplateModule::freeze(Origin::root()));
+
+		System::set_block_number(6);
+		Scheduler::on_initialize(6);
+		System::assert_has_event(
+			TemplateEvent::ValueRejected(1, 42, Error::<Test>::Frozen.into()).into(),
+		);
+		assert_eq!(TemplateModule::something_of(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(TemplateModule::scheduled(1), None);
+	});
+}
+
+#[test]
+fn scheduled_updates_are_limited() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::schedule_set(Origin::signed(UNFUNDED), 42, 5),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		// A block holds at most `MAX_UPDATES_PER_BLOCK` scheduled updates.
+		for who in 1..=MAX_UPDATES_PER_BLOCK as u64 {
+			assert_ok!(TemplateModule::schedule_set(Origin::signed(who), 42, 5));
+		}
+		let who = MAX_UPDATES_PER_BLOCK as u64 + 1;
+		assert_noop!(
+			TemplateModule::schedule_set(Origin::signed(who), 42, 5),
+			Error::<Test>::TooManyScheduled
+		);
+		assert_ok!(TemplateModule::schedule_set(Origin::signed(who), 42, 6));
+
+		// Cancelling an update makes room for another.
+		assert_ok!(TemplateModule::cancel_scheduled(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_ok!(TemplateModule::schedule_set(Origin::signed(1), 43, 5));
+	});
+}
+
+#[test]
+fn scheduled_updates_are_only_applied_when_due() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::apply_scheduled(Origin::signed(1)),
+			Error::<Test>::NotScheduled
+		);
+
+		assert_ok!(TemplateModule::schedule_set(Origin::signed(1), 42, 5));
+		assert_noop!(TemplateModule::apply_scheduled(Origin::signed(1)), Error::<Test>::NotDue);
+
+		// Once due, the caller may apply the update before the scheduler does.
+		System::set_block_number(6);
+		assert_ok!(TemplateModule::apply_scheduled(Origin::signed(1)));
+		assert_eq!(TemplateModule::something_of(1), Some(42));
+		assert_eq!(Balances::reserved_balance(1), DEPOSIT);
+		assert_noop!(
+			TemplateModule::apply_scheduled(Origin::signed(1)),
+			Error::<Test>::NotScheduled
+		);
+	});
+}
+
+#[test]
+fn scheduled_updates_can_be_cancelled() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::schedule_set(Origin::signed(1), 42, 0),
+			Error::<Test>::NoDelay
+		);
+		assert_noop!(
+			TemplateModule::cancel_scheduled(Origin::signed(1)),
+			Error::<Test>::NotScheduled
+		);
+
+		// An account has one scheduled update at a time.
+		assert_ok!(TemplateModule::schedule_set(Origin::signed(1), 42, 5));
+		assert_noop!(
+			TemplateModule::schedule_set(Origin::signed(1), 43, 10),
+			Error::<Test>::AlreadyScheduled
+		);
+		assert_ok!(TemplateModule::schedule_set(Origin::signed(2), 43, 5));
+
+		assert_ok!(TemplateModule::cancel_scheduled(Origin::signed(1)));
+		System::assert_last_event(TemplateEvent::ScheduledUpdateCancelled(1).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(TemplateModule::scheduled(1), None);
+
+		System::set_block_number(6);
+		Scheduler::on_initialize(6);
+		assert_eq!(TemplateModule::something_of(1), None);
+		assert_eq!(TemplateModule::something_of(2), Some(43));
+	});
+}
+
+// The account that is not endowed at genesis, and so cannot pay the deposit for a new entry.
+const UNFUNDED: u64 = 4;
+
+/// A call made in the property-based tests.
+#[derive(Clone, Debug)]
+enum Op {
+	DoSomething(u64, u32),
+	CauseError(u64),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+	let who = 1..=UNFUNDED;
+	// Values close to `u32::MAX` make overflows likely.
+	let value = prop_oneof![0..1_000u32, u32::MAX - 1_000..=u32::MAX];
+	prop_oneof![
+		(who.clone(), value).prop_map(|(who, value)| Op::DoSomething(who, value)),
+		who.prop_map(Op::CauseError),
+	]
+}
+
+/// The values the calls should leave in storage.
+#[derive(Default)]
+struct Model {
+	values: BTreeMap<u64, u32>,
+	total: Option<u64>,
+}
+
+impl Model {
+	/// Apply `op` to the model, returning the event the call should emit or the error it should
+	/// fail with.
+	fn apply(&mut self, op: &Op) -> Result<TemplateEvent<Test>, DispatchError> {
+		let (who, new) = match *op {
+			Op::DoSomething(who, _) if who == UNFUNDED && !self.values.contains_key(&who) =>
+				return Err(pallet_balances::Error::<Test>::InsufficientBalance.into()),
+			Op::DoSomething(who, value) => (who, value),
+			Op::CauseError(who) => {
+				let old = self.values.get(&who).ok_or(Error::<Test>::NoneValue)?;
+				(who, old.checked_add(1).ok_or(Error::<Test>::StorageOverflow)?)
+			},
+		};
+
+		let old = self.values.get(&who).copied();
+		let total =
+			self.total.unwrap_or_default() - u64::from(old.unwrap_or_default()) + u64::from(new);
+		self.values.insert(who, new);
+		self.total = Some(total);
+		Ok(TemplateEvent::SomethingStored(who, old, new))
+	}
+}
+
+proptest! {
+	#[test]
+	fn calls_match_the_model(ops in vec(op(), 1..50)) {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let mut model = Model::default();
+
+			for op in &ops {
+				let events = System::events().len();
+				let result = match *op {
+					Op::DoSomething(who, value) =>
+						TemplateModule::do_something(Origin::signed(who), value),
+					Op::CauseError(who) => TemplateModule::cause_error(Origin::signed(who)),
+				};
+
+				// Successful calls emit an event matching the new value, failed calls none.
+				match model.apply(op) {
+					Ok(event) => {
+						assert_ok!(result);
+						System::assert_last_event(event.into());
+					},
+					Err(error) => {
+						assert_eq!(result, Err(error));
+						assert_eq!(System::events().len(), events);
+					},
+				}
+
+				for who in 1..=UNFUNDED {
+					assert_eq!(TemplateModule::something_of(who), model.values.get(&who).copied());
+				}
+				assert_eq!(TemplateModule::something(), model.total);
+				let sum: u64 = crate::SomethingOf::<Test>::iter_values().map(u64::from).sum();
+				assert_eq!(sum, model.total.unwrap_or_default());
+			}
+		});
+	}
+}
@@ -0,0 +1,281 @@
+
+//! Estimated weights for pallet_template
+//!
+//! These weights are hand-written estimates, not benchmark results: the storage accesses follow
+//! each call's implementation, and the execution times are scaled from comparable FRAME
+//! pallets. Replace them with `./scripts/benchmark.sh pallet_template` on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_template.
+pub trait WeightInfo {
+	fn do_something() -> Weight;
+	fn cause_error() -> Weight;
+	fn cause_error_none_value() -> Weight;
+	fn clear() -> Weight;
+	fn force_set() -> Weight;
+	fn reset() -> Weight;
+	fn freeze() -> Weight;
+	fn unfreeze() -> Weight;
+	fn submit_value_unsigned() -> Weight;
+	fn schedule_set() -> Weight;
+	fn cancel_scheduled() -> Weight;
+	fn apply_scheduled() -> Weight;
+	fn on_initialize(p: u32, ) -> Weight;
+}
+
+/// Weights for pallet_template using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	// Storage: TemplateModule DepositOf (r:0 w:1)
+	fn do_something() -> Weight {
+		(42_955_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	fn cause_error() -> Weight {
+		(19_453_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:0)
+	fn cause_error_none_value() -> Weight {
+		(8_511_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule DepositOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear() -> Weight {
+		(39_704_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	fn force_set() -> Weight {
+		(20_027_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule DepositOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reset() -> Weight {
+		(38_422_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:0 w:1)
+	fn freeze() -> Weight {
+		(11_020_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:0 w:1)
+	fn unfreeze() -> Weight {
+		(10_874_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	// Storage: TemplateModule NextUnsignedAt (r:0 w:1)
+	// Storage: TemplateModule DepositOf (r:0 w:1)
+	fn submit_value_unsigned() -> Weight {
+		(45_318_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: TemplateModule Scheduled (r:1 w:1)
+	// Storage: TemplateModule ScheduledIn (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn schedule_set() -> Weight {
+		(46_381_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Scheduled (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: TemplateModule ScheduledIn (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_scheduled() -> Weight {
+		(48_215_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Scheduled (r:1 w:1)
+	// Storage: TemplateModule ScheduledIn (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	// Storage: TemplateModule DepositOf (r:0 w:1)
+	fn apply_scheduled() -> Weight {
+		(61_742_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: TemplateModule History (r:1 w:1)
+	fn on_initialize(p: u32, ) -> Weight {
+		(3_512_000 as Weight)
+			.saturating_add((18_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	// Storage: TemplateModule DepositOf (r:0 w:1)
+	fn do_something() -> Weight {
+		(42_955_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	fn cause_error() -> Weight {
+		(19_453_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:0)
+	fn cause_error_none_value() -> Weight {
+		(8_511_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule DepositOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear() -> Weight {
+		(39_704_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	fn force_set() -> Weight {
+		(20_027_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule DepositOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reset() -> Weight {
+		(38_422_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:0 w:1)
+	fn freeze() -> Weight {
+		(11_020_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:0 w:1)
+	fn unfreeze() -> Weight {
+		(10_874_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	// Storage: TemplateModule NextUnsignedAt (r:0 w:1)
+	// Storage: TemplateModule DepositOf (r:0 w:1)
+	fn submit_value_unsigned() -> Weight {
+		(45_318_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: TemplateModule Scheduled (r:1 w:1)
+	// Storage: TemplateModule ScheduledIn (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn schedule_set() -> Weight {
+		(46_381_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Scheduled (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: TemplateModule ScheduledIn (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_scheduled() -> Weight {
+		(48_215_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TemplateModule Scheduled (r:1 w:1)
+	// Storage: TemplateModule ScheduledIn (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TemplateModule Frozen (r:1 w:0)
+	// Storage: TemplateModule SomethingOf (r:1 w:1)
+	// Storage: TemplateModule Something (r:1 w:1)
+	// Storage: TemplateModule CounterForSomethingOf (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	// Storage: TemplateModule DepositOf (r:0 w:1)
+	fn apply_scheduled() -> Weight {
+		(61_742_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: TemplateModule History (r:1 w:1)
+	fn on_initialize(p: u32, ) -> Weight {
+		(3_512_000 as Weight)
+			.saturating_add((18_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
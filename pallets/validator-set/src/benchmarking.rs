@@ -0,0 +1,52 @@
+//! Benchmarking setup for pallet-validator-set
+
+use super::*;
+
+use crate::Pallet as ValidatorSet;
+use codec::Decode;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_runtime::traits::{StaticLookup, TrailingZeroInput};
+
+/// Give `who` session keys, which it needs to be added.
+fn set_keys<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+	let keys = T::Keys::decode(&mut TrailingZeroInput::zeroes()).map_err(|_| "invalid keys")?;
+	pallet_session::NextKeys::<T>::insert(who, keys);
+	Ok(())
+}
+
+/// Fill the validators up to `n` accounts.
+fn fill<T: Config>(n: u32) -> Result<(), &'static str> {
+	let origin = T::AddRemoveOrigin::successful_origin();
+	for i in ValidatorSet::<T>::validators().len() as u32..n {
+		let who: T::AccountId = account("validator", i, 0);
+		set_keys::<T>(&who)?;
+		ValidatorSet::<T>::add_validator(origin.clone(), T::Lookup::unlookup(who))?;
+	}
+	Ok(())
+}
+
+benchmarks! {
+	add_validator {
+		// The validator is added to `MaxValidators - 1` others, which it is checked against.
+		fill::<T>(T::MaxValidators::get() - 1)?;
+		let who: T::AccountId = account("new", 0, 0);
+		set_keys::<T>(&who)?;
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, T::Lookup::unlookup(who.clone()))
+	verify {
+		assert!(ValidatorSet::<T>::validators().contains(&who));
+	}
+
+	remove_validator {
+		// The last of `MaxValidators` validators is removed.
+		fill::<T>(T::MaxValidators::get())?;
+		let who = ValidatorSet::<T>::validators().last().cloned().ok_or("no validators")?;
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, T::Lookup::unlookup(who.clone()))
+	verify {
+		assert!(!ValidatorSet::<T>::validators().contains(&who));
+	}
+
+	impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,319 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Validator Set Pallet
+//!
+//! A permissioned validator set, for networks whose validators are known and trusted rather than
+//! elected by stake.
+//!
+//! `AddRemoveOrigin` adds and removes validators, and validators reported for an offence, such as
+//! being offline to `pallet_im_online`, are removed automatically, as long as `MinValidators`
+//! remain. The pallet is the `SessionManager` of `pallet_session`, which asks it for the validators
+//! of session `i + 2` when session `i` ends, and queues them for a session so that their keys are
+//! known in advance. A change to the set in session `i` therefore takes effect in session `i + 2`:
+//! an added validator starts validating, and a removed or reported one stops, a full session after
+//! the current one.
+//!
+//! The validators are accounts that set their session keys themselves, so the session pallet's
+//! `ValidatorId` is the account ID, and only accounts with session keys are added. The pallet also
+//! implements the historical `SessionManager` with each validator as its own full identification,
+//! so that offences can be reported against past sessions. Like `pallet_offences`, it remembers
+//! each offender reported for an offence in a time slot, so that the same offence is not reported
+//! twice, but only for `ReportedSessions` sessions: older reports are pruned when a session ends.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
+/// The log target of this pallet.
+const LOG_TARGET: &str = "runtime::validator-set";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{WeightInfo, LOG_TARGET};
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_session::historical;
+	use sp_runtime::traits::{Hash, StaticLookup};
+	use sp_staking::{
+		offence::{Offence, OffenceError, ReportOffence},
+		SessionIndex,
+	};
+	use sp_std::vec::Vec;
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_session::Config<ValidatorId = <Self as frame_system::Config>::AccountId>
+	{
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The origin that may add and remove validators.
+		type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+		/// The number of validators below which none are removed.
+		#[pallet::constant]
+		type MinValidators: Get<u32>;
+		/// The maximum number of validators.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+		/// The number of sessions, the current one included, for which reported offences are
+		/// remembered. Must be at least 1.
+		#[pallet::constant]
+		type ReportedSessions: Get<SessionIndex>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	// The validators, as they will be from the session after the current one ends.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+	// Whether `Validators` changed since the session pallet last queued it.
+	#[pallet::storage]
+	pub type Changed<T> = StorageValue<_, bool, ValueQuery>;
+
+	// The reported offences, by the session in which they were reported and the hash of the offence
+	// kind, time slot and offender.
+	#[pallet::storage]
+	pub type ReportedOffences<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, SessionIndex, Twox64Concat, T::Hash, ()>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The validators of the first sessions.
+		pub initial_validators: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { initial_validators: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let mut validators = BoundedVec::<T::AccountId, T::MaxValidators>::default();
+			for who in &self.initial_validators {
+				assert!(!validators.contains(who), "Validator is in the genesis config twice");
+				validators
+					.try_push(who.clone())
+					.expect("There are at most MaxValidators initial validators");
+			}
+			assert!(
+				validators.len() as u32 >= T::MinValidators::get(),
+				"There are at least MinValidators initial validators"
+			);
+			<Validators<T>>::put(validators);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator was added, from the session after the next one. [who]
+		ValidatorAdded(T::AccountId),
+		/// A validator was removed, from the session after the next one. [who]
+		ValidatorRemoved(T::AccountId),
+		/// A validator reported for an offence was removed, from the session after the next one.
+		/// [who]
+		OffenderRemoved(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already a validator.
+		AlreadyValidator,
+		/// The account is not a validator.
+		NotValidator,
+		/// There are already `MaxValidators` validators.
+		TooManyValidators,
+		/// There are only `MinValidators` validators.
+		TooFewValidators,
+		/// The account has not set its session keys.
+		NoSessionKeys,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add `who` to the validators, from the session after the next one.
+		///
+		/// `who` must have set its session keys.
+		#[pallet::weight(<T as Config>::WeightInfo::add_validator())]
+		pub fn add_validator(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			<Validators<T>>::try_mutate(|validators| {
+				ensure!(!validators.contains(&who), Error::<T>::AlreadyValidator);
+				ensure!(
+					pallet_session::NextKeys::<T>::contains_key(&who),
+					Error::<T>::NoSessionKeys
+				);
+				validators.try_push(who.clone()).map_err(|_| Error::<T>::TooManyValidators)
+			})?;
+			<Changed<T>>::put(true);
+
+			Self::deposit_event(Event::ValidatorAdded(who));
+			Ok(())
+		}
+
+		/// Remove `who` from the validators, from the session after the next one.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_validator())]
+		pub fn remove_validator(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			Self::remove(&who)?;
+
+			Self::deposit_event(Event::ValidatorRemoved(who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Remove `who` from the validators, unless only `MinValidators` are left.
+		fn remove(who: &T::AccountId) -> DispatchResult {
+			<Validators<T>>::try_mutate(|validators| {
+				let index =
+					validators.iter().position(|v| v == who).ok_or(Error::<T>::NotValidator)?;
+				ensure!(
+					validators.len() as u32 > T::MinValidators::get(),
+					Error::<T>::TooFewValidators
+				);
+				validators.remove(index);
+				Ok::<_, DispatchError>(())
+			})?;
+			<Changed<T>>::put(true);
+			Ok(())
+		}
+
+		/// The key under which `offender` is remembered as reported for offences of kind `O` in
+		/// `time_slot`.
+		fn report_id<O: Offence<(T::AccountId, T::AccountId)>>(
+			time_slot: &O::TimeSlot,
+			offender: &(T::AccountId, T::AccountId),
+		) -> T::Hash {
+			(O::ID, time_slot, offender).using_encoded(T::Hashing::hash)
+		}
+
+		/// Whether the offence under `report_id` was reported in the last `ReportedSessions`
+		/// sessions.
+		fn is_reported(report_id: &T::Hash) -> bool {
+			let current = <pallet_session::Pallet<T>>::current_index();
+			let first = current.saturating_add(1).saturating_sub(T::ReportedSessions::get());
+			(first..=current).any(|session| <ReportedOffences<T>>::contains_key(session, report_id))
+		}
+	}
+
+	impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+		/// Hand the validators to the session pallet if they changed.
+		fn new_session(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+			<Changed<T>>::take().then(|| Self::validators().into_inner())
+		}
+
+		fn new_session_genesis(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+			Some(Self::validators().into_inner())
+		}
+
+		/// Forget the offences reported in the session that leaves the last `ReportedSessions`.
+		fn end_session(end_index: SessionIndex) {
+			if let Some(expired) =
+				end_index.saturating_add(1).checked_sub(T::ReportedSessions::get())
+			{
+				let _ = <ReportedOffences<T>>::clear_prefix(expired, u32::MAX, None);
+			}
+		}
+
+		fn start_session(_start_index: SessionIndex) {}
+	}
+
+	impl<T: Config> historical::SessionManager<T::AccountId, T::AccountId> for Pallet<T> {
+		fn new_session(new_index: SessionIndex) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+			<Self as pallet_session::SessionManager<_>>::new_session(new_index)
+				.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+		}
+
+		fn new_session_genesis(
+			new_index: SessionIndex,
+		) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+			<Self as pallet_session::SessionManager<_>>::new_session_genesis(new_index)
+				.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+		}
+
+		fn end_session(end_index: SessionIndex) {
+			<Self as pallet_session::SessionManager<_>>::end_session(end_index)
+		}
+
+		fn start_session(_start_index: SessionIndex) {}
+	}
+
+	/// Removes the offenders of reported offences from the validators.
+	///
+	/// An offence is a duplicate, and changes nothing, if all of its offenders were already
+	/// reported for an offence of its kind in its time slot.
+	impl<T: Config, O: Offence<(T::AccountId, T::AccountId)>>
+		ReportOffence<T::AccountId, (T::AccountId, T::AccountId), O> for Pallet<T>
+	{
+		fn report_offence(_reporters: Vec<T::AccountId>, offence: O) -> Result<(), OffenceError> {
+			let time_slot = offence.time_slot();
+			let new_reports: Vec<_> = offence
+				.offenders()
+				.into_iter()
+				.map(|offender| (Self::report_id::<O>(&time_slot, &offender), offender.0))
+				.filter(|(report_id, _)| !Self::is_reported(report_id))
+				.collect();
+			if new_reports.is_empty() {
+				return Err(OffenceError::DuplicateReport)
+			}
+
+			let session = <pallet_session::Pallet<T>>::current_index();
+			for (report_id, who) in new_reports {
+				<ReportedOffences<T>>::insert(session, report_id, ());
+
+				match Self::remove(&who) {
+					Ok(()) => Self::deposit_event(Event::OffenderRemoved(who)),
+					// The offender was already removed.
+					Err(e) if e == Error::<T>::NotValidator.into() => {},
+					Err(e) => log::warn!(
+						target: LOG_TARGET,
+						"Offender {:?} stays a validator: {:?}",
+						who,
+						e
+					),
+				}
+			}
+			Ok(())
+		}
+
+		fn is_known_offence(
+			offenders: &[(T::AccountId, T::AccountId)],
+			time_slot: &O::TimeSlot,
+		) -> bool {
+			offenders
+				.iter()
+				.all(|offender| Self::is_reported(&Self::report_id::<O>(time_slot, offender)))
+		}
+	}
+}
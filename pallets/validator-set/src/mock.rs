@@ -0,0 +1,104 @@
+use crate as pallet_validator_set;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64};
+use frame_system::{self as system, EnsureRoot};
+use pallet_session::{PeriodicSessions, TestSessionHandler};
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, UintAuthorityId},
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
+	BuildStorage,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		ValidatorSet: pallet_validator_set::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Session: pallet_session::{Pallet, Call, Config<T>, Storage, Event},
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+/// The number of validators below which none are removed.
+pub const MIN_VALIDATORS: u32 = 2;
+
+/// The maximum number of validators.
+pub const MAX_VALIDATORS: u32 = 4;
+
+/// The number of sessions for which reported offences are remembered.
+pub const REPORTED_SESSIONS: u32 = 2;
+
+/// Sessions are rotated by hand with `Session::rotate_session`, never by block number.
+type NeverEndSession = PeriodicSessions<ConstU64<{ u64::MAX }>, ConstU64<0>>;
+
+impl pallet_session::Config for Test {
+	type Event = Event;
+	type ValidatorId = u64;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = NeverEndSession;
+	type NextSessionRotation = NeverEndSession;
+	type SessionManager = ValidatorSet;
+	type SessionHandler = TestSessionHandler;
+	type Keys = UintAuthorityId;
+	type WeightInfo = ();
+}
+
+impl pallet_validator_set::Config for Test {
+	type Event = Event;
+	type AddRemoveOrigin = EnsureRoot<u64>;
+	type MinValidators = ConstU32<MIN_VALIDATORS>;
+	type MaxValidators = ConstU32<MAX_VALIDATORS>;
+	type ReportedSessions = ConstU32<REPORTED_SESSIONS>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime, with accounts 1 to 3 as the validators,
+// and accounts 1 to 5 with session keys.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		system: Default::default(),
+		validator_set: pallet_validator_set::GenesisConfig { initial_validators: vec![1, 2, 3] },
+		session: pallet_session::GenesisConfig {
+			keys: (1..=5).map(|who| (who, who, UintAuthorityId(who))).collect(),
+		},
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+	// Events are not deposited in block 0.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
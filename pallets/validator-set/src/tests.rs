@@ -0,0 +1,219 @@
+use crate::{mock::*, Changed, Error, Event as ValidatorSetEvent, ReportedOffences};
+use frame_support::{assert_noop, assert_ok};
+use pallet_im_online::UnresponsivenessOffence;
+use pallet_session::{historical, SessionManager};
+use sp_runtime::{testing::UintAuthorityId, DispatchError};
+use sp_staking::offence::{OffenceError, ReportOffence};
+
+type Offline = UnresponsivenessOffence<(u64, u64)>;
+
+/// The validators the session pallet queues at the end of the current session, if they changed.
+fn new_session() -> Option<Vec<u64>> {
+	<ValidatorSet as SessionManager<u64>>::new_session(1)
+}
+
+/// The offence of `offenders` being offline in `session_index`.
+fn offline(session_index: u32, offenders: Vec<u64>) -> Offline {
+	UnresponsivenessOffence {
+		session_index,
+		validator_set_count: ValidatorSet::validators().len() as u32,
+		offenders: offenders.into_iter().map(|who| (who, who)).collect(),
+	}
+}
+
+/// Report `offenders` offline in `session_index`, as `pallet_im_online` does at the end of a
+/// session.
+fn report_offline(session_index: u32, offenders: Vec<u64>) {
+	assert_ok!(ValidatorSet::report_offence(vec![], offline(session_index, offenders)));
+}
+
+/// Whether `offenders` were all reported offline in `session_index`.
+fn is_known(offenders: &[u64], session_index: u32) -> bool {
+	let offenders: Vec<_> = offenders.iter().map(|&who| (who, who)).collect();
+	<ValidatorSet as ReportOffence<u64, (u64, u64), Offline>>::is_known_offence(
+		&offenders,
+		&session_index,
+	)
+}
+
+#[test]
+fn genesis_validators_start_the_first_session() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2, 3]);
+		assert_eq!(
+			<ValidatorSet as SessionManager<u64>>::new_session_genesis(0),
+			Some(vec![1, 2, 3])
+		);
+		assert_eq!(
+			<ValidatorSet as historical::SessionManager<u64, u64>>::new_session_genesis(0),
+			Some(vec![(1, 1), (2, 2), (3, 3)])
+		);
+		// Sessions keep the same validators until the set changes.
+		assert_eq!(new_session(), None);
+	});
+}
+
+#[test]
+fn add_validator_queues_the_new_set_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+
+		System::assert_last_event(ValidatorSetEvent::ValidatorAdded(4).into());
+		assert_eq!(new_session(), Some(vec![1, 2, 3, 4]));
+		assert_eq!(new_session(), None);
+	});
+}
+
+#[test]
+fn changes_take_effect_in_the_session_after_the_next() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Session::current_index(), 0);
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 1));
+
+		// The set is queued when session 0 ends, for session 2.
+		Session::rotate_session();
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		assert_eq!(
+			Session::queued_keys(),
+			vec![(2, UintAuthorityId(2)), (3, UintAuthorityId(3)), (4, UintAuthorityId(4))]
+		);
+
+		Session::rotate_session();
+		assert_eq!(Session::validators(), vec![2, 3, 4]);
+	});
+}
+
+#[test]
+fn add_validator_needs_session_keys() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::root(), 6), Error::<Test>::NoSessionKeys);
+
+		System::inc_providers(&6);
+		assert_ok!(Session::set_keys(Origin::signed(6), UintAuthorityId(6), vec![]));
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 6));
+	});
+}
+
+#[test]
+fn remove_validator_takes_effect_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+
+		System::assert_last_event(ValidatorSetEvent::ValidatorRemoved(2).into());
+		assert_eq!(
+			<ValidatorSet as historical::SessionManager<u64, u64>>::new_session(1),
+			Some(vec![(1, 1), (3, 3)])
+		);
+	});
+}
+
+#[test]
+fn only_the_add_remove_origin_changes_the_validators() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), DispatchError::BadOrigin);
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::signed(1), 2),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn add_validator_fails_for_validators_and_a_full_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 1),
+			Error::<Test>::AlreadyValidator
+		);
+
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 5),
+			Error::<Test>::TooManyValidators
+		);
+	});
+}
+
+#[test]
+fn remove_validator_keeps_min_validators() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 4),
+			Error::<Test>::NotValidator
+		);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 1));
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 2),
+			Error::<Test>::TooFewValidators
+		);
+	});
+}
+
+#[test]
+fn validators_reported_offline_are_removed() {
+	new_test_ext().execute_with(|| {
+		report_offline(0, vec![3]);
+
+		System::assert_last_event(ValidatorSetEvent::OffenderRemoved(3).into());
+		assert_eq!(new_session(), Some(vec![1, 2]));
+
+		// Reports of validators that were already removed change nothing.
+		report_offline(1, vec![3]);
+		assert!(!Changed::<Test>::get());
+	});
+}
+
+#[test]
+fn offenders_are_kept_to_leave_min_validators() {
+	new_test_ext().execute_with(|| {
+		report_offline(0, vec![1, 2, 3]);
+
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![2, 3]);
+	});
+}
+
+#[test]
+fn offences_are_reported_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		report_offline(0, vec![3]);
+		assert!(is_known(&[3], 0));
+		assert!(!is_known(&[3], 1));
+		assert!(!is_known(&[2, 3], 0));
+
+		// An offence whose offenders were all reported in its session is a duplicate.
+		assert_eq!(
+			ValidatorSet::report_offence(vec![], offline(0, vec![3])),
+			Err(OffenceError::DuplicateReport)
+		);
+
+		// An offence with a new offender is reported for that offender only.
+		report_offline(0, vec![2, 3]);
+		System::assert_last_event(ValidatorSetEvent::OffenderRemoved(2).into());
+		assert!(is_known(&[2, 3], 0));
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 4]);
+	});
+}
+
+#[test]
+fn reported_offences_are_forgotten_after_reported_sessions() {
+	new_test_ext().execute_with(|| {
+		report_offline(0, vec![3]);
+
+		for _ in 1..REPORTED_SESSIONS {
+			Session::rotate_session();
+		}
+		assert!(is_known(&[3], 0));
+		assert_eq!(
+			ValidatorSet::report_offence(vec![], offline(0, vec![3])),
+			Err(OffenceError::DuplicateReport)
+		);
+
+		// The report is pruned when the last session that remembers it ends.
+		Session::rotate_session();
+		assert!(!is_known(&[3], 0));
+		assert_eq!(ReportedOffences::<Test>::iter().count(), 0);
+	});
+}
@@ -0,0 +1,97 @@
+//! Adapters between the pallets of the runtime.
+
+use crate::{
+	AccountId, Assets, Authorship, Balances, Council, CouncilCollective, Runtime, Treasury,
+};
+use frame_support::traits::{
+	fungibles::{Balanced, CreditOf},
+	ContainsLengthBound, Currency, Get, OnUnbalanced, SortedMembers,
+};
+use pallet_asset_tx_payment::HandleCredit;
+use sp_std::vec::Vec;
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Pays imbalances to the author of the current block, or to the treasury if it is unknown.
+pub struct Author;
+
+impl OnUnbalanced<NegativeImbalance> for Author {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		match Authorship::author() {
+			Some(author) => Balances::resolve_creating(&author, amount),
+			None => Treasury::on_unbalanced(amount),
+		}
+	}
+}
+
+/// Splits transaction fees and tips between the treasury and the block author.
+///
+/// The treasury gets 80% of both and the author the rest.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let mut split = fees.ration(80, 20);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.ration_merge_into(80, 20, &mut split);
+			}
+			Treasury::on_unbalanced(split.0);
+			Author::on_unbalanced(split.1);
+		}
+	}
+}
+
+/// Splits transaction fees paid in an asset between the treasury and the block author, like
+/// `DealWithFees` does with the native token.
+///
+/// A share that would leave its recipient with less than the asset's minimum balance is burned.
+pub struct CreditToTreasuryAndAuthor;
+
+impl HandleCredit<AccountId, Assets> for CreditToTreasuryAndAuthor {
+	fn handle_credit(credit: CreditOf<AccountId, Assets>) {
+		let treasury = Treasury::account_id();
+		// Dropping the credit if it cannot be resolved burns it.
+		match Authorship::author() {
+			Some(author) => {
+				let to_treasury = credit.peek().saturating_mul(80) / 100;
+				let (to_treasury, to_author) = credit.split(to_treasury);
+				let _ = Assets::resolve(&treasury, to_treasury);
+				let _ = Assets::resolve(&author, to_author);
+			},
+			None => {
+				let _ = Assets::resolve(&treasury, credit);
+			},
+		}
+	}
+}
+
+/// The members of the council, who decide on the tips of `Tips`.
+pub struct CouncilTippers;
+
+impl SortedMembers<AccountId> for CouncilTippers {
+	fn sorted_members() -> Vec<AccountId> {
+		let mut members = Council::members();
+		members.sort();
+		members
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(who: &AccountId) {
+		pallet_collective::Members::<Runtime, CouncilCollective>::mutate(|members| {
+			if let Err(index) = members.binary_search(who) {
+				members.insert(index, who.clone());
+			}
+		});
+	}
+}
+
+impl ContainsLengthBound for CouncilTippers {
+	fn min_len() -> usize {
+		0
+	}
+
+	fn max_len() -> usize {
+		<Runtime as pallet_collective::Config<CouncilCollective>>::MaxMembers::get() as usize
+	}
+}
@@ -6,17 +6,31 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+mod impls;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_election_provider_support::{onchain, SequentialPhragmen};
+use impls::{CouncilTippers, CreditToTreasuryAndAuthor, DealWithFees};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
+use pallet_rate_limit::{RateLimit, RateLimits};
+use pallet_session::historical as pallet_session_historical;
+use scale_info::TypeInfo;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	create_runtime_str,
+	curve::PiecewiseLinear,
+	generic, impl_opaque_keys,
+	traits::{
+		self, AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount,
+		NumberFor, OpaqueKeys, StaticLookup, Verify,
+	},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, FixedPointNumber, MultiSignature, Perquintill, RuntimeDebug,
+	SaturatedConversion,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -27,25 +41,39 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, Contains, EitherOfDiverse,
+		EqualPrivilegeOnly, Everything, KeyOwnerProofSystem, NeverEnsureOrigin, Randomness,
+		StorageInfo, U128CurrencyToVote,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
-		IdentityFee, Weight,
+		IdentityFee, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+		WeightToFeePolynomial,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use frame_system::EnsureRoot;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::CurrencyAdapter;
+use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Percent, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
 
+/// The signed extension that rate-limits transactions.
+pub use pallet_rate_limit;
+
+/// The address type of claims in the genesis config.
+pub use pallet_claims::EthereumAddress;
+
+/// The types of validator keys and staker roles in the genesis config.
+pub use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+pub use pallet_staking::StakerStatus;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -85,12 +113,14 @@ pub mod opaque {
 		pub struct SessionKeys {
 			pub aura: Aura,
 			pub grandpa: Grandpa,
+			pub im_online: ImOnline,
 		}
 	}
 }
 
 // To learn more about runtime versioning and what each of the following value means:
 //   https://docs.substrate.io/v3/runtime/upgrades#runtime-versioning
+#[cfg(not(feature = "permissioned"))]
 #[sp_version::runtime_version]
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
@@ -99,12 +129,29 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	// This value started at 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
+	//   the compatible custom types. It is bumped with every change to the runtime, so that live
+	//   chains run the `Migrations` of the upgrade.
+	spec_version: 110,
+	impl_version: 1,
+	apis: RUNTIME_API_VERSIONS,
+	transaction_version: 9,
+	state_version: 1,
+};
+
+// With the `permissioned` feature, the runtime has a state machine of its own, so it has a
+// `spec_name` of its own: its nodes never execute the blocks of the staking runtime, and neither
+// runtime can be set as the code of the other's chains. Keep the versions in step with the above.
+#[cfg(feature = "permissioned")]
+#[sp_version::runtime_version]
+pub const VERSION: RuntimeVersion = RuntimeVersion {
+	spec_name: create_runtime_str!("node-template-permissioned"),
+	impl_name: create_runtime_str!("node-template-permissioned"),
+	authoring_version: 1,
+	spec_version: 110,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 9,
 	state_version: 1,
 };
 
@@ -200,17 +247,24 @@ impl frame_system::Config for Runtime {
 
 impl pallet_randomness_collective_flip::Config for Runtime {}
 
+/// The maximum number of validators, which are the Aura and GRANDPA authorities.
+pub const MAX_AUTHORITIES: u32 = 32;
+
+/// The authorities are the validators of the current session, less those disabled for offences.
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
-	type DisabledValidators = ();
-	type MaxAuthorities = ConstU32<32>;
+	type DisabledValidators = Session;
+	type MaxAuthorities = ConstU32<MAX_AUTHORITIES>;
 }
 
+/// Equivocating voters are reported with proofs that they owned their key in the session they
+/// voted in: to `Offences`, which has them slashed by `Staking`, or with the `permissioned` feature
+/// to `ValidatorSet`, which removes them.
 impl pallet_grandpa::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
 
-	type KeyOwnerProofSystem = ();
+	type KeyOwnerProofSystem = Historical;
 
 	type KeyOwnerProof =
 		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
@@ -220,10 +274,14 @@ impl pallet_grandpa::Config for Runtime {
 		GrandpaId,
 	)>>::IdentificationTuple;
 
-	type HandleEquivocation = ();
+	type HandleEquivocation = pallet_grandpa::EquivocationHandler<
+		Self::KeyOwnerIdentification,
+		ReportOffences,
+		ReportLongevity,
+	>;
 
 	type WeightInfo = ();
-	type MaxAuthorities = ConstU32<32>;
+	type MaxAuthorities = ConstU32<MAX_AUTHORITIES>;
 }
 
 impl pallet_timestamp::Config for Runtime {
@@ -234,6 +292,9 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// The minimum balance an account must hold to exist.
+pub const EXISTENTIAL_DEPOSIT: Balance = 500;
+
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ();
@@ -242,49 +303,749 @@ impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
-	type ExistentialDeposit = ConstU128<500>;
+	type DustRemoval = Treasury;
+	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 }
 
+/// The weight fee of a `Balances::transfer`, including the base weight of its extrinsic, which
+/// `WeightToFee` is calibrated to.
+///
+/// The whole fee of a transfer also includes its length fee, and is scaled by the fee multiplier.
+pub const TRANSFER_WEIGHT_FEE: Balance = 100_000_000;
+
+/// Converts weight to fee linearly, so that a `Balances::transfer` costs `TRANSFER_WEIGHT_FEE`.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+
+	fn polynomial() -> WeightToFeeCoefficients<Balance> {
+		use pallet_balances::WeightInfo;
+
+		let p = TRANSFER_WEIGHT_FEE;
+		let q = Balance::from(
+			ExtrinsicBaseWeight::get() +
+				<Runtime as pallet_balances::Config>::WeightInfo::transfer(),
+		);
+		vec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
+		}]
+		.into()
+	}
+}
+
+parameter_types! {
+	/// Fees rise while blocks are fuller than a quarter of their normal weight, and fall while
+	/// they are emptier.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// Fees grow by about 0.002% per full block, or almost 40% per day of full blocks.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	/// Fees never fall below a tenth of those `WeightToFee` is calibrated to.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10);
+}
+
 impl pallet_transaction_payment::Config for Runtime {
 	type Event = Event;
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
 	type OperationalFeeMultiplier = ConstU8<5>;
-	type WeightToFee = IdentityFee<Balance>;
+	type WeightToFee = WeightToFee;
 	type LengthToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+}
+
+/// The author of a block is the validator whose Aura key authored it.
+impl pallet_authorship::Config for Runtime {
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	type UncleGenerations = ConstU32<0>;
+	type FilterUncle = ();
+	/// Authoring earns era points and shows the validator is online.
+	type EventHandler = (Staking, ImOnline);
+}
+
+/// The number of blocks in a session, at the end of which the next validators take over.
+pub const SESSION_PERIOD: BlockNumber = HOURS;
+
+/// Sessions rotate every `SESSION_PERIOD` blocks.
+type SessionRotation = pallet_session::PeriodicSessions<ConstU32<SESSION_PERIOD>, ConstU32<0>>;
+
+/// The session manager, which chooses the validators of each session: `Staking` elects them, and
+/// with the `permissioned` feature `ValidatorSet` keeps those its origin added.
+#[cfg(not(feature = "permissioned"))]
+type ValidatorManager = Staking;
+#[cfg(feature = "permissioned")]
+type ValidatorManager = ValidatorSet;
+
+/// The pallet that the offences of validators are reported to.
+#[cfg(not(feature = "permissioned"))]
+type ReportOffences = Offences;
+#[cfg(feature = "permissioned")]
+type ReportOffences = ValidatorSet;
+
+/// The validators of each session are the stash accounts elected by `Staking`, which set their
+/// session keys from their controller accounts, or with the `permissioned` feature the accounts
+/// of `ValidatorSet`, which set their own session keys.
+impl pallet_session::Config for Runtime {
+	type Event = Event;
+	type ValidatorId = AccountId;
+	#[cfg(not(feature = "permissioned"))]
+	type ValidatorIdOf = pallet_staking::StashOf<Self>;
+	#[cfg(feature = "permissioned")]
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = SessionRotation;
+	type NextSessionRotation = SessionRotation;
+	type SessionManager = pallet_session_historical::NoteHistoricalRoot<Self, ValidatorManager>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_session_historical::Config for Runtime {
+	#[cfg(not(feature = "permissioned"))]
+	type FullIdentification = pallet_staking::Exposure<AccountId, Balance>;
+	#[cfg(not(feature = "permissioned"))]
+	type FullIdentificationOf = pallet_staking::ExposureOf<Runtime>;
+	#[cfg(feature = "permissioned")]
+	type FullIdentification = AccountId;
+	#[cfg(feature = "permissioned")]
+	type FullIdentificationOf = ConvertInto;
+}
+
+// Inflation is 10% a year while half of the issuance is staked, and falls to 2.5% as the staked
+// part moves away from half.
+pallet_staking_reward_curve::build! {
+	const REWARD_CURVE: PiecewiseLinear<'static> = curve!(
+		min_inflation: 0_025_000,
+		max_inflation: 0_100_000,
+		ideal_stake: 0_500_000,
+		falloff: 0_050_000,
+		max_piece_count: 40,
+		test_precision: 0_005_000,
+	);
+}
+
+parameter_types! {
+	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
+	/// A new era is forced once 17% of the validators have offended in the current one.
+	pub const OffendingValidatorsThreshold: Perbill = Perbill::from_percent(17);
+	pub const SessionsPerEra: u32 = 6;
+	pub const BondingDuration: u32 = 28;
+	/// Equivocation reports stay valid for as long as the offender's funds stay bonded.
+	pub const ReportLongevity: u64 =
+		BondingDuration::get() as u64 * SessionsPerEra::get() as u64 * SESSION_PERIOD as u64;
+}
+
+/// Elects validators with sequential Phragmén, in the runtime, at the end of each era.
+pub struct OnChainSeqPhragmen;
+
+impl onchain::Config for OnChainSeqPhragmen {
+	type System = Runtime;
+	type Solver = SequentialPhragmen<AccountId, Perbill>;
+	type DataProvider = Staking;
+	type WeightInfo = frame_election_provider_support::weights::SubstrateWeight<Runtime>;
+}
+
+impl onchain::BoundedConfig for OnChainSeqPhragmen {
+	type VotersBound = ConstU32<10_000>;
+	type TargetsBound = ConstU32<1_000>;
+}
+
+/// The numbers of stakers that staking benchmarks range over.
+pub struct StakingBenchmarkingConfig;
+
+impl pallet_staking::BenchmarkingConfig for StakingBenchmarkingConfig {
+	type MaxNominators = ConstU32<1_000>;
+	type MaxValidators = ConstU32<1_000>;
+}
+
+/// Anyone may bond funds and become a validator or nominate validators. Eras last six sessions,
+/// funds stay bonded for a week after unbonding, and slashes are applied a day less than that
+/// after the offence, unless half of the council cancels them first. Era payouts are minted, and
+/// the part of the inflation that does not go to stakers goes to the treasury.
+///
+/// With the `permissioned` feature, `Staking` does not manage the sessions, so no eras start and
+/// no validators are elected.
+impl pallet_staking::Config for Runtime {
+	type MaxNominations = ConstU32<16>;
+	type Currency = Balances;
+	type CurrencyBalance = Balance;
+	type UnixTime = Timestamp;
+	type CurrencyToVote = U128CurrencyToVote;
+	type RewardRemainder = Treasury;
+	type Event = Event;
+	type Slash = Treasury;
+	type Reward = ();
+	type SessionsPerEra = SessionsPerEra;
+	type BondingDuration = BondingDuration;
+	type SlashDeferDuration = ConstU32<27>;
+	type SlashCancelOrigin = EnsureRootOrHalfCouncil;
+	#[cfg(not(feature = "permissioned"))]
+	type SessionInterface = Self;
+	#[cfg(feature = "permissioned")]
+	type SessionInterface = ();
+	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
+	type NextNewSession = Session;
+	type MaxNominatorRewardedPerValidator = ConstU32<256>;
+	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
+	type ElectionProvider = onchain::BoundedExecution<OnChainSeqPhragmen>;
+	type GenesisElectionProvider = onchain::UnboundedExecution<OnChainSeqPhragmen>;
+	type VoterList = pallet_staking::UseNominatorsAndValidatorsMap<Self>;
+	type MaxUnlockingChunks = ConstU32<32>;
+	type OnStakerSlash = ();
+	type BenchmarkingConfig = StakingBenchmarkingConfig;
+	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
+}
+
+/// Offences are reported against the validators of past sessions, and slashed by `Staking`.
+///
+/// With the `permissioned` feature, offences are reported to `ValidatorSet` instead.
+impl pallet_offences::Config for Runtime {
+	type Event = Event;
+	type IdentificationTuple = pallet_session_historical::IdentificationTuple<Self>;
+	#[cfg(not(feature = "permissioned"))]
+	type OnOffenceHandler = Staking;
+	#[cfg(feature = "permissioned")]
+	type OnOffenceHandler = ();
+}
+
+/// Validators that neither author a block nor send a heartbeat in a session are reported as
+/// unresponsive.
+impl pallet_im_online::Config for Runtime {
+	type AuthorityId = ImOnlineId;
+	type Event = Event;
+	type NextSessionRotation = SessionRotation;
+	type ValidatorSet = Historical;
+	type ReportUnresponsiveness = ReportOffences;
+	type UnsignedPriority = ConstU64<{ TransactionPriority::max_value() }>;
+	type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
+	type MaxKeys = ConstU32<MAX_AUTHORITIES>;
+	type MaxPeerInHeartbeats = ConstU32<10_000>;
+	type MaxPeerDataEncodingSize = ConstU32<1_000>;
+}
+
+/// With the `permissioned` feature, half of the council adds and removes validators, and reports
+/// of offences are remembered for as long as an era of `Staking` would last.
+#[cfg(feature = "permissioned")]
+impl pallet_validator_set::Config for Runtime {
+	type Event = Event;
+	type AddRemoveOrigin = EnsureRootOrHalfCouncil;
+	type MinValidators = ConstU32<1>;
+	type MaxValidators = ConstU32<MAX_AUTHORITIES>;
+	type ReportedSessions = SessionsPerEra;
+	type WeightInfo = pallet_validator_set::weights::SubstrateWeight<Runtime>;
+}
+
+/// The identifier of an asset.
+pub type AssetId = u32;
+
+/// Creating an asset or setting its metadata is root-only, as is making an asset sufficient, which
+/// lets accounts hold it without the native token and pay fees in it.
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<{ 10_000 * EXISTENTIAL_DEPOSIT }>;
+	type MetadataDepositBase = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type MetadataDepositPerByte = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type ApprovalDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+/// Fees may be paid in any sufficient asset instead of the native token. The fee in the native
+/// token is converted at the rate of the asset's minimum balance to the existential deposit, so
+/// `ForceOrigin` sets the rate of an asset along with its minimum balance.
+impl pallet_asset_tx_payment::Config for Runtime {
+	type Event = Event;
+	type Fungibles = Assets;
+	type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+		pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+		CreditToTreasuryAndAuthor,
+	>;
+}
+
+parameter_types! {
+	/// Each proposed call may use up to a quarter of a block, so that the two proposals that may
+	/// end in a block use up to half of it.
+	pub MaxProposalWeight: Weight = Perbill::from_percent(25) * BlockWeights::get().max_block;
+}
+
+/// The call that the `QuadraticVoting` benchmarks propose.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct FreezeTemplate;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl frame_support::traits::Get<Call> for FreezeTemplate {
+	fn get() -> Call {
+		Call::TemplateModule(pallet_template::Call::freeze {})
+	}
+}
+
+/// Passed proposals dispatch their calls with the root origin. Calls heavier than
+/// `MaxProposalWeight`, such as runtime upgrades, are left to `Democracy`.
+impl pallet_quadratic_voting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Proposal = Call;
+	type CallFilter = Everything;
+	type VotingPeriod = ConstU32<HOURS>;
+	type ProposalDeposit = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	/// Ten votes cost as much as a proposal.
+	type VoteUnit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	/// At least as many tokens as a thousand single votes cost must be reserved for votes on a
+	/// proposal.
+	type MinTurnout = ConstU128<{ 10_000 * EXISTENTIAL_DEPOSIT }>;
+	type MaxProposals = ConstU32<50>;
+	type MaxEndingPerBlock = ConstU32<2>;
+	type MaxProposalWeight = MaxProposalWeight;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkProposal = FreezeTemplate;
+	type WeightInfo = pallet_quadratic_voting::weights::SubstrateWeight<Runtime>;
+}
+
+/// The council, which proposes referenda and manages the technical committee.
+pub type CouncilCollective = pallet_collective::Instance1;
+
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = ConstU32<{ 3 * DAYS }>;
+	type MaxProposals = ConstU32<100>;
+	type MaxMembers = ConstU32<100>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// The technical committee, which fast-tracks and vetoes referenda.
+pub type TechnicalCollective = pallet_collective::Instance2;
+
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = ConstU32<{ 3 * DAYS }>;
+	type MaxProposals = ConstU32<100>;
+	type MaxMembers = ConstU32<100>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root, or more than half of the council.
+pub type EnsureRootOrHalfCouncil = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
+>;
+
+/// Root, or all of the technical committee.
+pub type EnsureRootOrAllTechnicalCommittee = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 1>,
+>;
+
+/// The origin that may approve spends from a treasury: root, or three fifths of the council.
+pub type TreasurySpendOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+>;
+
+/// The origin that may table a runtime upgrade for an immediate referendum: root, or two thirds of
+/// the technical committee.
+///
+/// `System::set_code` itself needs root, which an enacted referendum dispatches with. The council
+/// proposes upgrades as external referenda, which this origin may fast-track.
+pub type RuntimeUpgradeOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>,
+>;
+
+/// The council manages the members of the technical committee.
+impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrHalfCouncil;
+	type RemoveOrigin = EnsureRootOrHalfCouncil;
+	type SwapOrigin = EnsureRootOrHalfCouncil;
+	type ResetOrigin = EnsureRootOrHalfCouncil;
+	type PrimeOrigin = EnsureRootOrHalfCouncil;
+	type MembershipInitialized = TechnicalCommittee;
+	type MembershipChanged = TechnicalCommittee;
+	type MaxMembers = ConstU32<100>;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+/// Referenda change the members of the council, and the council chooses its prime member.
+impl pallet_membership::Config<pallet_membership::Instance2> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRoot<AccountId>;
+	type RemoveOrigin = EnsureRoot<AccountId>;
+	type SwapOrigin = EnsureRoot<AccountId>;
+	type ResetOrigin = EnsureRoot<AccountId>;
+	type PrimeOrigin = EnsureRootOrHalfCouncil;
+	type MembershipInitialized = Council;
+	type MembershipChanged = Council;
+	type MaxMembers = ConstU32<100>;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = ConstU32<DAYS>;
+	type LaunchPeriod = ConstU32<{ 7 * DAYS }>;
+	type VotingPeriod = ConstU32<{ 7 * DAYS }>;
+	type VoteLockingPeriod = ConstU32<DAYS>;
+	type MinimumDeposit = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	/// More than half of the council decides on the next external referendum.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>;
+	/// Three quarters of the council may make it pass by a simple majority.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>;
+	/// The whole council may make it pass unless a majority votes against it.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>;
+	type FastTrackOrigin = RuntimeUpgradeOrigin;
+	type InstantOrigin = EnsureRootOrAllTechnicalCommittee;
+	type InstantAllowed = ConstBool<true>;
+	type FastTrackVotingPeriod = ConstU32<{ 3 * HOURS }>;
+	/// Two thirds of the council may cancel a referendum that is already running.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+	type CancelProposalOrigin = EnsureRootOrAllTechnicalCommittee;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// Any member of the technical committee may veto an external proposal once, for the
+	/// cool-off period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type CooloffPeriod = ConstU32<{ 7 * DAYS }>;
+	type PreimageByteDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = ConstU32<100>;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = ConstU32<100>;
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const CuratorDepositMultiplier: Permill = Permill::from_percent(50);
+	pub const CuratorDepositMin: Option<Balance> = Some(1_000 * EXISTENTIAL_DEPOSIT);
+	pub const CuratorDepositMax: Option<Balance> = Some(100_000 * EXISTENTIAL_DEPOSIT);
+	pub const TipFindersFee: Percent = Percent::from_percent(20);
+}
+
+/// The treasury is funded by transaction fees, dust and slashed deposits.
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = TreasurySpendOrigin;
+	type RejectOrigin = EnsureRootOrHalfCouncil;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = ConstU32<DAYS>;
+	/// Unspent funds are kept for later spend periods.
+	type Burn = ();
+	type BurnDestination = ();
+	type SpendFunds = Bounties;
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = ConstU32<100>;
+	/// Spends go through proposals approved by `ApproveOrigin`.
+	type SpendOrigin = NeverEnsureOrigin<Balance>;
 }
 
-impl pallet_sudo::Config for Runtime {
+impl pallet_bounties::Config for Runtime {
 	type Event = Event;
+	type BountyDepositBase = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type BountyDepositPayoutDelay = ConstU32<DAYS>;
+	type BountyUpdatePeriod = ConstU32<{ 14 * DAYS }>;
+	type CuratorDepositMultiplier = CuratorDepositMultiplier;
+	type CuratorDepositMin = CuratorDepositMin;
+	type CuratorDepositMax = CuratorDepositMax;
+	type BountyValueMinimum = ConstU128<{ 2_000 * EXISTENTIAL_DEPOSIT }>;
+	type DataDepositPerByte = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type MaximumReasonLength = ConstU32<300>;
+	type WeightInfo = pallet_bounties::weights::SubstrateWeight<Runtime>;
+	type ChildBountyManager = ();
+}
+
+impl pallet_tips::Config for Runtime {
+	type Event = Event;
+	type DataDepositPerByte = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type MaximumReasonLength = ConstU32<300>;
+	type Tippers = CouncilTippers;
+	type TipCountdown = ConstU32<DAYS>;
+	type TipFindersFee = TipFindersFee;
+	type TipReportDepositBase = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type WeightInfo = pallet_tips::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type MaxSize = ConstU32<{ 4096 * 1024 }>;
+	type BaseDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type ByteDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+}
+
+parameter_types! {
+	/// Scheduled calls may use up to 80% of a block.
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		BlockWeights::get().max_block;
+	/// Retry a scheduled call every ten blocks until its preimage is noted.
+	pub const NoPreimagePostponement: Option<BlockNumber> = Some(10);
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
 	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = NoPreimagePostponement;
 }
 
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type Event = Event;
+	type Currency = Balances;
+	/// Each account's entry holds a hundred times the existential deposit.
+	type StorageDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	/// Only root, e.g. a passed `QuadraticVoting` proposal, may overwrite values and freeze writes.
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxHistory = ConstU32<100>;
+	/// Keep a day's worth of history.
+	type HistoryRetention = ConstU32<DAYS>;
+	/// The offchain worker signs with the `tmpl` sr25519 key in the node's keystore.
+	type AuthorityId = pallet_template::crypto::TemplateAuthId;
+	/// Only the members of the technical committee may submit fee-free unsigned values.
+	type OffchainAuthorities = TechnicalMembership;
+	/// The offchain worker submits a value every ten blocks.
+	type OffchainInterval = ConstU32<10>;
+	type UnsignedPriority = ConstU64<{ TransactionPriority::max_value() / 2 }>;
+	type ScheduledCall = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	/// Scheduling an update holds ten times the existential deposit until it is dispatched.
+	type ScheduleDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	/// The template pallet may use a fifth of the scheduler's calls in any block.
+	type MaxUpdatesPerBlock = ConstU32<10>;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_kitties::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	/// A kitty holds ten times the existential deposit for as long as it exists.
+	type KittyDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type MaxKittiesOwned = ConstU32<100>;
+	type KittyRandomness = RandomnessCollectiveFlip;
+	type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_poe::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ClaimDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	// Long enough for a SHA-512 digest.
+	type MaxClaimLength = ConstU32<64>;
+	type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+parameter_types! {
+	/// Claimants sign this followed by the hex-encoded account their allocation is paid to.
+	pub ClaimsPrefix: &'static [u8] = b"Pay node template tokens to the account:";
+}
+
+/// Allocations are paid out with their vesting schedules by `Vesting`. Only root may add them
+/// after genesis.
+impl pallet_claims::Config for Runtime {
+	type Event = Event;
+	type VestingSchedule = Vesting;
+	type Prefix = ClaimsPrefix;
+	type MintOrigin = EnsureRoot<AccountId>;
+	type UnsignedPriority = ConstU64<{ TransactionPriority::max_value() / 2 }>;
+	type WeightInfo = pallet_claims::weights::SubstrateWeight<Runtime>;
+}
+
+/// The categories of calls whose transactions are rate-limited per account.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RateLimitCategory {
+	/// The template pallet's calls that write the caller's value.
+	TemplateWrites,
+	/// The template pallet's calls that schedule or cancel an update of the caller's value.
+	TemplateSchedules,
+}
+
+/// Limits the template pallet's signed calls, which are cheap enough to spam.
+pub struct TemplateRateLimits;
+
+impl RateLimits<Call, BlockNumber> for TemplateRateLimits {
+	type Category = RateLimitCategory;
+
+	fn rate_limit(call: &Call) -> Option<(RateLimitCategory, RateLimit<BlockNumber>)> {
+		use pallet_template::Call as TemplateCall;
+
+		match call {
+			Call::TemplateModule(
+				TemplateCall::do_something { .. } |
+				TemplateCall::cause_error {} |
+				TemplateCall::clear {} |
+				TemplateCall::apply_scheduled {},
+			) => Some((RateLimitCategory::TemplateWrites, RateLimit { max: 5, period: MINUTES })),
+			Call::TemplateModule(
+				TemplateCall::schedule_set { .. } | TemplateCall::cancel_scheduled {},
+			) => Some((RateLimitCategory::TemplateSchedules, RateLimit { max: 2, period: MINUTES })),
+			_ => None,
+		}
+	}
+}
+
+impl pallet_rate_limit::Config for Runtime {
+	type RateLimits = TemplateRateLimits;
 }
 
-// Create the runtime by composing the FRAME pallets that were previously configured.
-construct_runtime!(
-	pub enum Runtime where
-		Block = Block,
-		NodeBlock = opaque::Block,
-		UncheckedExtrinsic = UncheckedExtrinsic
-	{
-		System: frame_system,
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
-		Timestamp: pallet_timestamp,
-		Aura: pallet_aura,
-		Grandpa: pallet_grandpa,
-		Balances: pallet_balances,
-		TransactionPayment: pallet_transaction_payment,
-		Sudo: pallet_sudo,
-		// Include the custom logic from the pallet-template in the runtime.
-		TemplateModule: pallet_template,
+/// Lets offchain workers sign transactions with the runtime's `SignedExtra`.
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as traits::Extrinsic>::SignaturePayload)> {
+		let tip = 0;
+		// Take the biggest period possible.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number()
+			.saturated_into::<u64>()
+			// The `System::block_number` is initialized with `n+1`, so the actual block number
+			// is `n`.
+			.saturating_sub(1);
+		let era = generic::Era::mortal(period, current_block);
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(era),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
+			pallet_rate_limit::CheckRateLimit::<Runtime>::new(),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let address = <Runtime as frame_system::Config>::Lookup::unlookup(account);
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (address, signature, extra)))
 	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
+}
+
+/// Create the runtime by composing the FRAME pallets that were previously configured, with the
+/// `extra` pallets of this build between `Staking` and `Session`.
+macro_rules! construct_node_runtime {
+	($($extra:tt)*) => {
+		construct_runtime!(
+			pub enum Runtime where
+				Block = Block,
+				NodeBlock = opaque::Block,
+				UncheckedExtrinsic = UncheckedExtrinsic
+			{
+				System: frame_system = 0,
+				RandomnessCollectiveFlip: pallet_randomness_collective_flip = 1,
+				Timestamp: pallet_timestamp = 2,
+				Aura: pallet_aura = 3,
+				Grandpa: pallet_grandpa = 4,
+				Balances: pallet_balances = 5,
+				TransactionPayment: pallet_transaction_payment = 6,
+				// Index 7 belonged to `Sudo`. It stays unused, so that old transactions and events that
+				// refer to it are never read as those of another pallet.
+				// Include the custom logic from the pallet-template in the runtime.
+				TemplateModule: pallet_template = 8,
+				QuadraticVoting: pallet_quadratic_voting = 9,
+				Preimage: pallet_preimage = 10,
+				Scheduler: pallet_scheduler = 11,
+				Poe: pallet_poe = 12,
+				Kitties: pallet_kitties = 13,
+				Council: pallet_collective::<Instance1> = 14,
+				TechnicalCommittee: pallet_collective::<Instance2> = 15,
+				TechnicalMembership: pallet_membership::<Instance1> = 16,
+				CouncilMembership: pallet_membership::<Instance2> = 17,
+				Democracy: pallet_democracy = 18,
+				Authorship: pallet_authorship = 19,
+				Treasury: pallet_treasury = 20,
+				Bounties: pallet_bounties = 21,
+				Tips: pallet_tips = 22,
+				Assets: pallet_assets = 23,
+				AssetTxPayment: pallet_asset_tx_payment = 24,
+				Vesting: pallet_vesting = 25,
+				Claims: pallet_claims = 26,
+				Staking: pallet_staking = 27,
+				// Index 28 is `ValidatorSet`'s, with the `permissioned` feature.
+				$($extra)*
+				Session: pallet_session = 29,
+				Historical: pallet_session_historical::{Pallet} = 30,
+				Offences: pallet_offences = 31,
+				ImOnline: pallet_im_online = 32,
+				RateLimit: pallet_rate_limit = 33,
+			}
+		);
+	};
+}
+
+#[cfg(not(feature = "permissioned"))]
+construct_node_runtime!();
+#[cfg(feature = "permissioned")]
+construct_node_runtime!(
+	// Before `Session`, so that its genesis validators are set when `Session` asks for them.
+	ValidatorSet: pallet_validator_set = 28,
 );
 
 /// The address format for describing accounts.
@@ -302,12 +1063,28 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
+	pallet_rate_limit::CheckRateLimit<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
+/// Storage migrations to run on the next runtime upgrade, before the pallets' own
+/// `on_runtime_upgrade` hooks.
+///
+/// Register the migrations that a new runtime version needs here, and remove them again once the
+/// upgrade has been applied to every live chain.
+pub type Migrations = (
+	pallet_template::migrations::v1::MigrateToV1<Runtime>,
+	pallet_template::migrations::v2::MigrateToV2<Runtime>,
+	pallet_template::migrations::v3::MigrateToV3<Runtime>,
+	pallet_template::migrations::v4::MigrateToV4<Runtime>,
+	pallet_kitties::migrations::v1::MigrateToV1<Runtime>,
+	pallet_quadratic_voting::migrations::v1::MigrateToV1<Runtime>,
+	RemoveSudoKey,
+	BootstrapValidators,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -315,21 +1092,181 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Removes the key of the `Sudo` pallet, which the council, technical committee and referenda of
+/// `Democracy` replaced as the source of the root origin.
+pub struct RemoveSudoKey;
+
+impl frame_support::traits::OnRuntimeUpgrade for RemoveSudoKey {
+	fn on_runtime_upgrade() -> Weight {
+		use frame_support::storage::{storage_prefix, unhashed};
+
+		let key = storage_prefix(b"Sudo", b"Key");
+		if !unhashed::exists(&key) {
+			return RocksDbWeight::get().reads(1)
+		}
+		unhashed::kill(&key);
+		RocksDbWeight::get().reads_writes(1, 1)
+	}
+}
+
+/// Makes the Aura and GRANDPA authorities of a chain that ran without `Session` its validators.
+///
+/// A chain built from the genesis of this runtime has its validators in its chain spec. A chain
+/// that upgrades to it from a runtime with a fixed set of authorities has no session validators
+/// and no session keys, so the first session rotation would leave Aura and GRANDPA without
+/// authorities, and the chain would halt. The migration does nothing if `Session` has validators.
+///
+/// The authorities of Aura and GRANDPA are paired in the order they are stored, which is the order
+/// of the chain spec. The account of each validator is the account of its Aura key, and its
+/// ImOnline key is its Aura key as well, so the operators must also insert the Aura key into their
+/// keystore with the `imon` key type, or set new session keys with `Session::set_keys`.
+///
+/// Each validator bonds half of its free balance to validate, so that `Staking` elects validators
+/// from the first session rotation. A validator that cannot bond stays a validator until others
+/// are elected. With the `permissioned` feature, the validators become those of `ValidatorSet`.
+pub struct BootstrapValidators;
+
+impl frame_support::traits::OnRuntimeUpgrade for BootstrapValidators {
+	fn on_runtime_upgrade() -> Weight {
+		use frame_support::traits::OneSessionHandler;
+
+		if !Session::validators().is_empty() {
+			return RocksDbWeight::get().reads(1)
+		}
+		let validators: Vec<(AccountId, opaque::SessionKeys)> = Aura::authorities()
+			.into_iter()
+			.zip(Grandpa::grandpa_authorities())
+			.filter_map(|(aura, (grandpa, _))| {
+				let account = AccountId::decode(&mut &aura.encode()[..]).ok()?;
+				let im_online = ImOnlineId::decode(&mut &aura.encode()[..]).ok()?;
+				Some((account, opaque::SessionKeys { aura, grandpa, im_online }))
+			})
+			.collect();
+
+		for (account, keys) in &validators {
+			// Like the genesis of `Session`, let validators without funds hold their keys.
+			if !System::account_exists(account) {
+				System::inc_providers(account);
+			}
+			let _ = System::inc_consumers_without_limit(account);
+			for id in <opaque::SessionKeys as OpaqueKeys>::key_ids() {
+				pallet_session::KeyOwner::<Runtime>::insert(
+					(*id, keys.get_raw(*id).to_vec()),
+					account,
+				);
+			}
+			pallet_session::NextKeys::<Runtime>::insert(account, keys);
+
+			#[cfg(not(feature = "permissioned"))]
+			{
+				let origin = || Origin::signed(account.clone());
+				let _ = Staking::bond(
+					origin(),
+					sp_runtime::MultiAddress::Id(account.clone()),
+					Balances::free_balance(account) / 2,
+					pallet_staking::RewardDestination::Staked,
+				)
+				.and_then(|_| Staking::validate(origin(), Default::default()));
+			}
+			#[cfg(feature = "permissioned")]
+			let _ = pallet_validator_set::Validators::<Runtime>::try_append(account);
+		}
+
+		let accounts: Vec<AccountId> =
+			validators.iter().map(|(account, _)| account.clone()).collect();
+		pallet_session::Validators::<Runtime>::put(&accounts);
+		pallet_session::QueuedKeys::<Runtime>::put(&validators);
+		#[cfg(not(feature = "permissioned"))]
+		{
+			pallet_staking::ValidatorCount::<Runtime>::put(MAX_AUTHORITIES);
+			pallet_staking::MinimumValidatorCount::<Runtime>::put(1);
+		}
+		if ImOnline::keys().is_empty() {
+			ImOnline::on_genesis_session(
+				validators.iter().map(|(account, keys)| (account, keys.im_online.clone())),
+			);
+		}
+		BlockWeights::get().max_block
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+		let authorities =
+			if Session::validators().is_empty() { Aura::authorities().len() as u32 } else { 0 };
+		Self::set_temp_storage(authorities, "authorities");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+		let authorities: u32 = Self::get_temp_storage("authorities")
+			.ok_or("the number of authorities was not recorded before the migration")?;
+		let validators = Session::validators();
+		frame_support::ensure!(!validators.is_empty(), "there must be session validators");
+		if authorities == 0 {
+			return Ok(())
+		}
+		frame_support::ensure!(
+			validators.len() as u32 == authorities,
+			"every authority must become a validator"
+		);
+		frame_support::ensure!(
+			validators.iter().all(|v| pallet_session::NextKeys::<Runtime>::contains_key(v)) &&
+				Session::queued_keys().len() == validators.len() &&
+				ImOnline::keys().len() == validators.len(),
+			"every validator must have session keys"
+		);
+		Ok(())
+	}
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
-	define_benchmarks!(
-		[frame_benchmarking, BaselineBench::<Runtime>]
-		[frame_system, SystemBench::<Runtime>]
-		[pallet_balances, Balances]
-		[pallet_timestamp, Timestamp]
-		[pallet_template, TemplateModule]
-	);
+	/// Define the benchmarks of the pallets of every build, and the `extra` ones of this build.
+	macro_rules! define_node_benchmarks {
+		($($extra:tt)*) => {
+			define_benchmarks!(
+				[frame_benchmarking, BaselineBench::<Runtime>]
+				[frame_system, SystemBench::<Runtime>]
+				[pallet_assets, Assets]
+				[pallet_balances, Balances]
+				[pallet_bounties, Bounties]
+				[pallet_claims, Claims]
+				[pallet_collective, Council]
+				[pallet_democracy, Democracy]
+				[pallet_im_online, ImOnline]
+				[pallet_kitties, Kitties]
+				[pallet_membership, TechnicalMembership]
+				[pallet_poe, Poe]
+				[pallet_preimage, Preimage]
+				[pallet_quadratic_voting, QuadraticVoting]
+				[pallet_scheduler, Scheduler]
+				[pallet_staking, Staking]
+				[pallet_timestamp, Timestamp]
+				[pallet_template, TemplateModule]
+				[pallet_tips, Tips]
+				[pallet_treasury, Treasury]
+				$($extra)*
+				[pallet_vesting, Vesting]
+			);
+		};
+	}
+
+	#[cfg(not(feature = "permissioned"))]
+	define_node_benchmarks!();
+	#[cfg(feature = "permissioned")]
+	define_node_benchmarks!([pallet_validator_set, ValidatorSet]);
 }
 
 impl_runtime_apis! {
@@ -422,23 +1359,24 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
+			equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+			key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(equivocation_proof, key_owner_proof)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((fg_primitives::KEY_TYPE, authority_id))
+				.map(|proof| proof.encode())
+				.map(fg_primitives::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -463,6 +1401,36 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+		fn claim(
+			hash: Vec<u8>,
+		) -> Option<pallet_poe_runtime_api::ClaimInfo<AccountId, BlockNumber, Balance>> {
+			Poe::claim_of(hash).map(|claim| pallet_poe_runtime_api::ClaimInfo {
+				owner: claim.owner,
+				block: claim.block,
+				deposit: claim.deposit,
+			})
+		}
+	}
+
+	impl pallet_template_runtime_api::TemplateApi<Block, AccountId> for Runtime {
+		fn something() -> Option<u64> {
+			TemplateModule::something()
+		}
+
+		fn something_of(who: AccountId) -> Option<u32> {
+			TemplateModule::something_of(who)
+		}
+
+		fn stats() -> pallet_template_runtime_api::TemplateStats {
+			pallet_template_runtime_api::TemplateStats {
+				something: TemplateModule::something(),
+				accounts: pallet_template::SomethingOf::<Runtime>::count(),
+				frozen: TemplateModule::is_frozen(),
+			}
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,610 @@
+//! Integration tests of the whole runtime.
+//!
+//! The tests build genesis from the runtime's `GenesisConfig` and run signed extrinsics with the
+//! full `SignedExtra` through `Executive`, one block at a time, the way a node imports them.
+//!
+//! They test the runtime in which `Staking` elects the validators; `permissioned.rs` tests the one
+//! built with the `permissioned` feature.
+#![cfg(not(feature = "permissioned"))]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_ok,
+	dispatch::{DispatchError, GetDispatchInfo},
+	storage::{storage_prefix, unhashed},
+	traits::{Get, KeyOwnerProofSystem, OnRuntimeUpgrade, PalletInfo as _},
+};
+use node_template_runtime::{
+	opaque::SessionKeys, pallet_rate_limit, pallet_template, AssetId, Assets, AssetsConfig, Aura,
+	Balance, BalancesCall, BalancesConfig, BlockNumber, BootstrapValidators, Call,
+	CouncilCollective, CouncilMembershipConfig, Event, Executive, GenesisConfig, Grandpa, Hash,
+	Header, Historical, ImOnline, Index, Runtime, Session, SessionConfig, SignedExtra,
+	SignedPayload, StakerStatus, Staking, StakingConfig, System, TemplateModule, TimestampCall,
+	TransactionPayment, Treasury, UncheckedExtrinsic, EXISTENTIAL_DEPOSIT, SLOT_DURATION,
+};
+use pallet_grandpa::fg_primitives;
+use sp_consensus_aura::{sr25519::AuthorityId as AuraId, Slot, AURA_ENGINE_ID};
+use sp_keyring::{AccountKeyring, Ed25519Keyring};
+use sp_runtime::{
+	generic::Era,
+	traits::Header as _,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	ApplyExtrinsicResult, BuildStorage, Digest, DigestItem, MultiAddress,
+};
+
+/// The balance of the accounts endowed at genesis.
+const ENDOWMENT: Balance = 1 << 60;
+
+/// A sufficient asset, worth ten times less than the native token.
+const STABLE_ASSET_ID: AssetId = 1;
+
+/// Build the genesis storage, with Alice as the only validator, of two that may be elected, and
+/// the only council member, Alice and Bob endowed, and Bob also holding `STABLE_ASSET_ID`.
+fn new_test_ext() -> sp_io::TestExternalities {
+	let alice = AccountKeyring::Alice.to_account_id();
+	GenesisConfig {
+		system: Default::default(),
+		aura: Default::default(),
+		grandpa: Default::default(),
+		im_online: Default::default(),
+		session: SessionConfig {
+			keys: vec![(
+				alice.clone(),
+				alice.clone(),
+				SessionKeys {
+					aura: AccountKeyring::Alice.public().into(),
+					grandpa: Ed25519Keyring::Alice.public().into(),
+					im_online: AccountKeyring::Alice.public().into(),
+				},
+			)],
+		},
+		staking: StakingConfig {
+			validator_count: 2,
+			minimum_validator_count: 1,
+			stakers: vec![(alice.clone(), alice, ENDOWMENT / 2, StakerStatus::Validator)],
+			..Default::default()
+		},
+		balances: BalancesConfig {
+			balances: vec![
+				(AccountKeyring::Alice.to_account_id(), ENDOWMENT),
+				(AccountKeyring::Bob.to_account_id(), ENDOWMENT),
+			],
+		},
+		transaction_payment: Default::default(),
+		council: Default::default(),
+		technical_committee: Default::default(),
+		technical_membership: Default::default(),
+		council_membership: CouncilMembershipConfig {
+			members: vec![AccountKeyring::Alice.to_account_id()],
+			phantom: Default::default(),
+		},
+		democracy: Default::default(),
+		treasury: Default::default(),
+		template_module: Default::default(),
+		kitties: Default::default(),
+		assets: AssetsConfig {
+			assets: vec![(STABLE_ASSET_ID, AccountKeyring::Alice.to_account_id(), true, 50)],
+			metadata: vec![],
+			accounts: vec![(STABLE_ASSET_ID, AccountKeyring::Bob.to_account_id(), ENDOWMENT)],
+		},
+		vesting: Default::default(),
+		claims: Default::default(),
+	}
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+/// Sign `call` by `signer` with the runtime's `SignedExtra`, paying fees in the native token.
+fn sign(call: Call, signer: AccountKeyring, nonce: Index) -> UncheckedExtrinsic {
+	sign_paying_in(call, signer, nonce, None)
+}
+
+/// Sign `call` by `signer` with the runtime's `SignedExtra`, paying fees in `asset_id`, or in the
+/// native token if it is `None`.
+fn sign_paying_in(
+	call: Call,
+	signer: AccountKeyring,
+	nonce: Index,
+	asset_id: Option<AssetId>,
+) -> UncheckedExtrinsic {
+	let extra: SignedExtra = (
+		frame_system::CheckNonZeroSender::<Runtime>::new(),
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+		frame_system::CheckNonce::<Runtime>::from(nonce),
+		frame_system::CheckWeight::<Runtime>::new(),
+		pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(0, asset_id),
+		pallet_rate_limit::CheckRateLimit::<Runtime>::new(),
+	);
+	let payload = SignedPayload::new(call, extra).unwrap();
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+	let (call, extra, _) = payload.deconstruct();
+	UncheckedExtrinsic::new_signed(
+		call,
+		MultiAddress::Id(signer.to_account_id()),
+		signature.into(),
+		extra,
+	)
+}
+
+/// Execute block `number` on top of `parent_hash`: authored by Alice in slot `number`, with the
+/// timestamp inherent followed by `extrinsics`.
+///
+/// Returns the results of applying `extrinsics` and the hash of the block.
+fn execute_block(
+	number: BlockNumber,
+	parent_hash: Hash,
+	extrinsics: Vec<UncheckedExtrinsic>,
+) -> (Vec<ApplyExtrinsicResult>, Hash) {
+	let slot = Slot::from(u64::from(number));
+	let digest = Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode())] };
+	Executive::initialize_block(&Header::new(
+		number,
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		digest,
+	));
+
+	let now = *slot * SLOT_DURATION;
+	let timestamp = UncheckedExtrinsic::new_unsigned(Call::Timestamp(TimestampCall::set { now }));
+	assert_ok!(Executive::apply_extrinsic(timestamp));
+
+	let results = extrinsics.into_iter().map(Executive::apply_extrinsic).collect();
+	(results, Executive::finalize_block().hash())
+}
+
+/// The fee `who` paid for the last extrinsic that paid one, as reported by its event.
+fn last_fee_paid_by(who: &AccountKeyring) -> Balance {
+	System::events()
+		.into_iter()
+		.rev()
+		.find_map(|record| match record.event {
+			Event::TransactionPayment(pallet_transaction_payment::Event::TransactionFeePaid {
+				who: payer,
+				actual_fee,
+				..
+			}) if payer == who.to_account_id() => Some(actual_fee),
+			_ => None,
+		})
+		.expect("a fee was paid")
+}
+
+/// The part of `fee` that goes to the block author rather than the treasury.
+fn author_share(fee: Balance) -> Balance {
+	fee - fee * 80 / 100
+}
+
+#[test]
+fn signed_transfer_pays_fees_and_bumps_the_nonce() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountKeyring::Alice, AccountKeyring::Bob);
+		let amount = 1_000 * EXISTENTIAL_DEPOSIT;
+		let xt = sign(
+			Call::Balances(BalancesCall::transfer {
+				dest: bob.to_account_id().into(),
+				value: amount,
+			}),
+			alice,
+			0,
+		);
+		let expected_fee =
+			TransactionPayment::compute_fee(xt.encode().len() as u32, &xt.get_dispatch_info(), 0);
+
+		let (results, _) = execute_block(1, System::block_hash(0), vec![xt]);
+		assert_ok!(results[0].clone().unwrap());
+
+		let fee = last_fee_paid_by(&alice);
+		assert!(fee > 0);
+		assert_eq!(fee, expected_fee);
+		assert_eq!(System::account_nonce(alice.to_account_id()), 1);
+		assert_eq!(System::account_nonce(bob.to_account_id()), 0);
+		// Alice authored the block, so the author's share of the fee came back to her.
+		assert_eq!(
+			pallet_balances::Pallet::<Runtime>::free_balance(alice.to_account_id()),
+			ENDOWMENT - amount - fee + author_share(fee)
+		);
+		assert_eq!(
+			pallet_balances::Pallet::<Runtime>::free_balance(bob.to_account_id()),
+			ENDOWMENT + amount
+		);
+	});
+}
+
+#[test]
+fn template_calls_run_through_signed_extrinsics() {
+	new_test_ext().execute_with(|| {
+		let alice = AccountKeyring::Alice;
+		let who = alice.to_account_id();
+		let do_something = |something, nonce| {
+			sign(
+				Call::TemplateModule(pallet_template::Call::do_something { something }),
+				alice,
+				nonce,
+			)
+		};
+
+		let (results, parent_hash) =
+			execute_block(1, System::block_hash(0), vec![do_something(42, 0)]);
+		assert_ok!(results[0].clone().unwrap());
+		assert_eq!(TemplateModule::something_of(&who), Some(42));
+		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(TemplateModule::deposit_of(&who), Some(100 * EXISTENTIAL_DEPOSIT));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::TemplateModule(pallet_template::Event::SomethingStored(
+				who.clone(),
+				None,
+				42
+			))));
+
+		// The next block updates the value, without taking another deposit.
+		let (results, _) = execute_block(2, parent_hash, vec![do_something(43, 1)]);
+		assert_ok!(results[0].clone().unwrap());
+		assert_eq!(TemplateModule::something_of(&who), Some(43));
+		assert_eq!(
+			pallet_balances::Pallet::<Runtime>::reserved_balance(&who),
+			100 * EXISTENTIAL_DEPOSIT
+		);
+		assert_eq!(System::account_nonce(&who), 2);
+	});
+}
+
+#[test]
+fn failed_calls_still_pay_fees_and_bump_the_nonce() {
+	new_test_ext().execute_with(|| {
+		let alice = AccountKeyring::Alice;
+		let xt = sign(Call::TemplateModule(pallet_template::Call::cause_error {}), alice, 0);
+
+		let (results, _) = execute_block(1, System::block_hash(0), vec![xt]);
+		assert_eq!(
+			results[0].clone().unwrap(),
+			Err(DispatchError::from(pallet_template::Error::<Runtime>::NoneValue))
+		);
+		assert!(last_fee_paid_by(&alice) > 0);
+		assert_eq!(System::account_nonce(alice.to_account_id()), 1);
+	});
+}
+
+#[test]
+fn invalid_transactions_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountKeyring::Alice, AccountKeyring::Bob);
+		let call = || Call::TemplateModule(pallet_template::Call::do_something { something: 1 });
+
+		// Signed by Bob on behalf of Alice.
+		let mut forged = sign(call(), alice, 0);
+		let bobs = sign(call(), bob, 0);
+		forged.signature.as_mut().unwrap().1 = bobs.signature.unwrap().1;
+
+		let (results, _) = execute_block(
+			1,
+			System::block_hash(0),
+			vec![forged, sign(call(), alice, 1), sign(call(), alice, 0), sign(call(), alice, 0)],
+		);
+		assert_eq!(
+			results[0],
+			Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof))
+		);
+		assert_eq!(results[1], Err(TransactionValidityError::Invalid(InvalidTransaction::Future)));
+		assert_ok!(results[2].clone().unwrap());
+		assert_eq!(results[3], Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)));
+		assert_eq!(System::account_nonce(alice.to_account_id()), 1);
+	});
+}
+
+#[test]
+fn excess_template_calls_are_rate_limited() {
+	new_test_ext().execute_with(|| {
+		let alice = AccountKeyring::Alice;
+		let do_something = |nonce| {
+			sign(
+				Call::TemplateModule(pallet_template::Call::do_something { something: 1 }),
+				alice,
+				nonce,
+			)
+		};
+
+		// Five writes per minute are accepted, the sixth is not.
+		let (results, parent_hash) =
+			execute_block(1, System::block_hash(0), (0..6).map(do_something).collect());
+		results[..5].iter().for_each(|result| assert_ok!(result.clone().unwrap()));
+		assert_eq!(
+			results[5],
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(
+				pallet_rate_limit::RATE_LIMITED
+			)))
+		);
+		assert_eq!(System::account_nonce(alice.to_account_id()), 5);
+
+		// Other calls are not limited.
+		let transfer = sign(
+			Call::Balances(BalancesCall::transfer {
+				dest: AccountKeyring::Bob.to_account_id().into(),
+				value: EXISTENTIAL_DEPOSIT,
+			}),
+			alice,
+			5,
+		);
+		let (results, _) = execute_block(2, parent_hash, vec![transfer]);
+		assert_ok!(results[0].clone().unwrap());
+	});
+}
+
+#[test]
+fn the_baseline_pallets_keep_their_indices() {
+	type Info = <Runtime as frame_system::Config>::PalletInfo;
+	assert_eq!(Info::index::<System>(), Some(0));
+	assert_eq!(Info::index::<TransactionPayment>(), Some(6));
+	assert_eq!(Info::index::<TemplateModule>(), Some(8));
+
+	// The index of the removed sudo pallet is not reused.
+	assert!(Call::decode(&mut &[7u8, 0][..]).is_err());
+	// `ValidatorSet` is only in the runtime of the `permissioned` feature.
+	assert!(Call::decode(&mut &[28u8, 0][..]).is_err());
+}
+
+#[test]
+fn council_motions_table_external_referenda() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountKeyring::Alice, AccountKeyring::Bob);
+		let external = Call::Democracy(pallet_democracy::Call::external_propose_majority {
+			proposal_hash: Hash::repeat_byte(1),
+		});
+		let propose = |signer, nonce| {
+			sign(
+				Call::Council(pallet_collective::Call::propose {
+					threshold: 1,
+					proposal: Box::new(external.clone()),
+					length_bound: external.encoded_size() as u32,
+				}),
+				signer,
+				nonce,
+			)
+		};
+
+		// Only council members may propose, and Alice alone is enough to pass the motion.
+		let (results, _) =
+			execute_block(1, System::block_hash(0), vec![propose(bob, 0), propose(alice, 0)]);
+		assert_eq!(
+			results[0].clone().unwrap(),
+			Err(DispatchError::from(
+				pallet_collective::Error::<Runtime, CouncilCollective>::NotMember
+			))
+		);
+		assert_ok!(results[1].clone().unwrap());
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::Council(pallet_collective::Event::Executed { result: Ok(()), .. })
+		)));
+		assert!(pallet_democracy::NextExternal::<Runtime>::exists());
+	});
+}
+
+#[test]
+fn fees_are_split_between_the_treasury_and_the_block_author() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountKeyring::Bob;
+		let pot = Treasury::pot();
+		let xt = sign(
+			Call::TemplateModule(pallet_template::Call::do_something { something: 1 }),
+			bob,
+			0,
+		);
+
+		let (results, _) = execute_block(1, System::block_hash(0), vec![xt]);
+		assert_ok!(results[0].clone().unwrap());
+
+		let fee = last_fee_paid_by(&bob);
+		assert_eq!(Treasury::pot(), pot + fee - author_share(fee));
+		assert_eq!(
+			pallet_balances::Pallet::<Runtime>::free_balance(AccountKeyring::Alice.to_account_id()),
+			ENDOWMENT + author_share(fee)
+		);
+	});
+}
+
+#[test]
+fn dust_goes_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountKeyring::Bob;
+		let pot = Treasury::pot();
+		let transfer = |value| {
+			sign(
+				Call::Balances(BalancesCall::transfer {
+					dest: AccountKeyring::Charlie.to_account_id().into(),
+					value,
+				}),
+				bob,
+				0,
+			)
+		};
+		// The fee does not depend on the value, which is encoded in as many bytes either way.
+		let xt = transfer(ENDOWMENT);
+		let fee =
+			TransactionPayment::compute_fee(xt.encode().len() as u32, &xt.get_dispatch_info(), 0);
+
+		// Leave Bob with a single unit, below the existential deposit.
+		let (results, _) =
+			execute_block(1, System::block_hash(0), vec![transfer(ENDOWMENT - fee - 1)]);
+		assert_ok!(results[0].clone().unwrap());
+
+		assert_eq!(last_fee_paid_by(&bob), fee);
+		assert_eq!(pallet_balances::Pallet::<Runtime>::total_balance(&bob.to_account_id()), 0);
+		assert_eq!(Treasury::pot(), pot + fee - author_share(fee) + 1);
+	});
+}
+
+#[test]
+fn fees_can_be_paid_in_a_sufficient_asset() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountKeyring::Alice, AccountKeyring::Bob);
+		let amount = 1_000 * EXISTENTIAL_DEPOSIT;
+		let xt = sign_paying_in(
+			Call::Balances(BalancesCall::transfer {
+				dest: alice.to_account_id().into(),
+				value: amount,
+			}),
+			bob,
+			0,
+			Some(STABLE_ASSET_ID),
+		);
+		let native_fee =
+			TransactionPayment::compute_fee(xt.encode().len() as u32, &xt.get_dispatch_info(), 0);
+
+		let (results, parent_hash) = execute_block(1, System::block_hash(0), vec![xt]);
+		assert_ok!(results[0].clone().unwrap());
+
+		// The minimum balance of the asset is a tenth of the existential deposit, and so is the
+		// fee.
+		let fee = native_fee / 10;
+		assert!(fee > 0);
+		assert_eq!(Assets::balance(STABLE_ASSET_ID, bob.to_account_id()), ENDOWMENT - fee);
+		assert_eq!(
+			pallet_balances::Pallet::<Runtime>::free_balance(bob.to_account_id()),
+			ENDOWMENT - amount
+		);
+		assert_eq!(System::account_nonce(bob.to_account_id()), 1);
+
+		// Paying in an asset the account does not hold is invalid.
+		let xt = sign_paying_in(
+			Call::TemplateModule(pallet_template::Call::do_something { something: 1 }),
+			alice,
+			0,
+			Some(STABLE_ASSET_ID),
+		);
+		let (results, _) = execute_block(2, parent_hash, vec![xt]);
+		assert_eq!(results[0], Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)));
+	});
+}
+
+#[test]
+fn bonded_validators_are_elected_and_take_over_authoring() {
+	new_test_ext().execute_with(|| {
+		let bob = AccountKeyring::Bob;
+		let keys = SessionKeys {
+			aura: bob.public().into(),
+			grandpa: Ed25519Keyring::Bob.public().into(),
+			im_online: bob.public().into(),
+		};
+		let calls = vec![
+			Call::Staking(pallet_staking::Call::bond {
+				controller: MultiAddress::Id(bob.to_account_id()),
+				value: ENDOWMENT / 2,
+				payee: pallet_staking::RewardDestination::Staked,
+			}),
+			Call::Session(pallet_session::Call::set_keys { keys, proof: vec![] }),
+			Call::Staking(pallet_staking::Call::validate { prefs: Default::default() }),
+		];
+		let xts = calls.into_iter().zip(0..).map(|(call, nonce)| sign(call, bob, nonce)).collect();
+
+		let (results, _) = execute_block(1, System::block_hash(0), xts);
+		results.iter().for_each(|result| assert_ok!(result.clone().unwrap()));
+		assert_eq!(Session::validators(), vec![AccountKeyring::Alice.to_account_id()]);
+
+		// Bob is elected at the end of the era, and validates from the first session of the next.
+		let sessions_per_era = <Runtime as pallet_staking::Config>::SessionsPerEra::get();
+		for _ in 0..sessions_per_era {
+			Session::rotate_session();
+		}
+		assert!(Session::validators().contains(&bob.to_account_id()));
+		assert!(Aura::authorities().contains(&bob.public().into()));
+	});
+}
+
+#[test]
+fn grandpa_equivocations_are_reported_as_offences() {
+	new_test_ext().execute_with(|| {
+		let alice = Ed25519Keyring::Alice;
+		let set_id = Grandpa::current_set_id();
+		// Alice prevotes for two different blocks in the same round.
+		let prevote = |target_hash| {
+			let prevote = finality_grandpa::Prevote { target_hash, target_number: 1 };
+			let payload = fg_primitives::localized_payload(
+				1,
+				set_id,
+				&finality_grandpa::Message::Prevote(prevote.clone()),
+			);
+			(prevote, fg_primitives::AuthoritySignature::from(alice.sign(&payload)))
+		};
+		let equivocation_proof = fg_primitives::EquivocationProof::new(
+			set_id,
+			fg_primitives::Equivocation::Prevote(finality_grandpa::Equivocation {
+				round_number: 1,
+				identity: alice.public().into(),
+				first: prevote(Hash::repeat_byte(1)),
+				second: prevote(Hash::repeat_byte(2)),
+			}),
+		);
+		let key_owner_proof = Historical::prove((
+			fg_primitives::KEY_TYPE,
+			fg_primitives::AuthorityId::from(alice.public()),
+		))
+		.unwrap();
+		let report = || {
+			UncheckedExtrinsic::new_unsigned(Call::Grandpa(
+				pallet_grandpa::Call::report_equivocation_unsigned {
+					equivocation_proof: Box::new(equivocation_proof.clone()),
+					key_owner_proof: key_owner_proof.clone(),
+				},
+			))
+		};
+
+		// The same offence is only reported once.
+		let (results, _) = execute_block(1, System::block_hash(0), vec![report(), report()]);
+		assert_ok!(results[0].clone().unwrap());
+		assert_eq!(results[1], Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)));
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::Offences(pallet_offences::Event::Offence { .. })
+		)));
+		// Alice's slash is deferred, so that the council may cancel it.
+		assert_eq!(pallet_staking::UnappliedSlashes::<Runtime>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn the_authorities_of_a_chain_without_session_become_its_validators() {
+	new_test_ext().execute_with(|| {
+		// A chain that ran Alice and Bob as its Aura and GRANDPA authorities, without `Session`.
+		for pallet in [&b"Session"[..], b"Historical", b"Staking", b"ImOnline"] {
+			let _ = unhashed::clear_prefix(&sp_io::hashing::twox_128(pallet), None, None);
+		}
+		let aura: Vec<AuraId> =
+			vec![AccountKeyring::Alice.public().into(), AccountKeyring::Bob.public().into()];
+		unhashed::put(&storage_prefix(b"Aura", b"Authorities"), &aura);
+		let grandpa: fg_primitives::AuthorityList = vec![
+			(Ed25519Keyring::Alice.public().into(), 1),
+			(Ed25519Keyring::Bob.public().into(), 1),
+		];
+		unhashed::put(
+			fg_primitives::GRANDPA_AUTHORITIES_KEY,
+			&fg_primitives::VersionedAuthorityList::from(grandpa),
+		);
+		let (alice, bob) =
+			(AccountKeyring::Alice.to_account_id(), AccountKeyring::Bob.to_account_id());
+		assert!(Session::validators().is_empty());
+
+		BootstrapValidators::on_runtime_upgrade();
+		assert_eq!(Session::validators(), vec![alice.clone(), bob.clone()]);
+		assert_eq!(ImOnline::keys().len(), 2);
+		assert_eq!(
+			Session::next_keys(&bob).map(|keys| keys.grandpa),
+			Some(Ed25519Keyring::Bob.public().into())
+		);
+
+		// Both are elected at the first session rotation, and keep authoring after it.
+		Session::rotate_session();
+		Session::rotate_session();
+		assert_eq!(Staking::eras_stakers(0, &alice).total, ENDOWMENT / 2);
+		assert_eq!(Staking::eras_stakers(0, &bob).total, ENDOWMENT / 2);
+		assert_eq!(Aura::authorities().into_inner(), aura);
+		assert_eq!(Grandpa::grandpa_authorities().len(), 2);
+
+		// The migration does nothing once there are validators.
+		BootstrapValidators::on_runtime_upgrade();
+		assert_eq!(Session::validators(), vec![alice, bob]);
+	});
+}
@@ -0,0 +1,88 @@
+//! Simulations of the runtime's fee multiplier over many blocks, and the calibration of its weight
+//! fee.
+
+use frame_support::weights::{
+	constants::ExtrinsicBaseWeight, DispatchClass, Weight, WeightToFee as _,
+};
+use node_template_runtime::{
+	BlockWeights, MinimumMultiplier, Runtime, System, TargetBlockFullness, WeightToFee, DAYS,
+	TRANSFER_WEIGHT_FEE,
+};
+use pallet_balances::WeightInfo;
+use pallet_transaction_payment::Multiplier;
+use sp_runtime::{traits::Convert, FixedPointNumber};
+
+type FeeMultiplierUpdate = <Runtime as pallet_transaction_payment::Config>::FeeMultiplierUpdate;
+
+/// The maximum weight of the normal dispatch class in a block.
+fn max_normal() -> Weight {
+	let weights = BlockWeights::get();
+	weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block)
+}
+
+/// The weight of a block as full as `TargetBlockFullness`.
+fn target() -> Weight {
+	TargetBlockFullness::get() * max_normal()
+}
+
+/// The multipliers after each of `blocks` blocks that used `weight`, starting from `multiplier`.
+fn simulate(multiplier: Multiplier, weight: Weight, blocks: u32) -> Vec<Multiplier> {
+	sp_io::TestExternalities::default().execute_with(|| {
+		System::set_block_consumed_resources(weight, 0);
+		(0..blocks)
+			.scan(multiplier, |multiplier, _| {
+				*multiplier = FeeMultiplierUpdate::convert(*multiplier);
+				Some(*multiplier)
+			})
+			.collect()
+	})
+}
+
+#[test]
+fn transfer_weight_costs_the_calibrated_fee() {
+	let weight =
+		ExtrinsicBaseWeight::get() + <Runtime as pallet_balances::Config>::WeightInfo::transfer();
+	let fee = WeightToFee::weight_to_fee(&weight);
+	assert!(fee.abs_diff(TRANSFER_WEIGHT_FEE) <= 1, "{} is not {}", fee, TRANSFER_WEIGHT_FEE);
+	// The fee is linear in the weight.
+	assert!(WeightToFee::weight_to_fee(&(2 * weight)).abs_diff(2 * fee) <= 1);
+}
+
+#[test]
+fn multiplier_is_stable_at_target_fullness() {
+	let one = Multiplier::saturating_from_integer(1);
+	let multipliers = simulate(one, target(), DAYS);
+	assert!(multipliers.iter().all(|multiplier| *multiplier == one));
+}
+
+#[test]
+fn multiplier_grows_over_full_blocks() {
+	let multipliers = simulate(Multiplier::saturating_from_integer(1), max_normal(), DAYS);
+
+	assert!(multipliers.windows(2).all(|pair| pair[0] < pair[1]));
+	// A day of full blocks raises fees by more than a third, but less than half.
+	let last = *multipliers.last().unwrap();
+	assert!(last > Multiplier::saturating_from_rational(4, 3), "{:?}", last);
+	assert!(last < Multiplier::saturating_from_rational(3, 2), "{:?}", last);
+}
+
+#[test]
+fn multiplier_falls_to_the_minimum_over_empty_blocks() {
+	let multipliers = simulate(Multiplier::saturating_from_integer(1), 0, 30 * DAYS);
+
+	// Fees fall steadily until they reach the minimum, then stay there.
+	let reached = multipliers
+		.iter()
+		.position(|multiplier| *multiplier == MinimumMultiplier::get())
+		.expect("the multiplier reaches the minimum");
+	assert!(multipliers[..reached].windows(2).all(|pair| pair[0] > pair[1]));
+	assert!(multipliers[reached..]
+		.iter()
+		.all(|multiplier| *multiplier == MinimumMultiplier::get()));
+	// Three weeks of empty blocks is not quite enough.
+	assert!(reached > (21 * DAYS) as usize, "{}", reached);
+
+	// Full blocks raise fees from the minimum again.
+	let multipliers = simulate(MinimumMultiplier::get(), max_normal(), 1);
+	assert!(multipliers[0] > MinimumMultiplier::get());
+}
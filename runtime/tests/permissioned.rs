@@ -0,0 +1,96 @@
+//! Integration tests of the runtime built with the `permissioned` feature, in which `ValidatorSet`
+//! rather than `Staking` chooses the validators.
+#![cfg(feature = "permissioned")]
+
+use frame_support::{assert_noop, assert_ok, traits::PalletInfo as _};
+use node_template_runtime::{
+	opaque::SessionKeys, Aura, Balance, BalancesConfig, GenesisConfig, Origin, Runtime, Session,
+	SessionConfig, ValidatorSet, ValidatorSetConfig, VERSION,
+};
+use sp_keyring::{AccountKeyring, Ed25519Keyring};
+use sp_runtime::{BuildStorage, MultiAddress};
+
+/// The balance of the accounts endowed at genesis.
+const ENDOWMENT: Balance = 1 << 60;
+
+/// The session keys of `who`.
+fn session_keys(who: AccountKeyring, ed: Ed25519Keyring) -> SessionKeys {
+	SessionKeys {
+		aura: who.public().into(),
+		grandpa: ed.public().into(),
+		im_online: who.public().into(),
+	}
+}
+
+/// Build the genesis storage, with Alice as the only validator, and Alice and Bob endowed.
+fn new_test_ext() -> sp_io::TestExternalities {
+	let alice = AccountKeyring::Alice.to_account_id();
+	GenesisConfig {
+		system: Default::default(),
+		aura: Default::default(),
+		grandpa: Default::default(),
+		im_online: Default::default(),
+		validator_set: ValidatorSetConfig { initial_validators: vec![alice.clone()] },
+		session: SessionConfig {
+			keys: vec![(
+				alice.clone(),
+				alice,
+				session_keys(AccountKeyring::Alice, Ed25519Keyring::Alice),
+			)],
+		},
+		staking: Default::default(),
+		balances: BalancesConfig {
+			balances: vec![
+				(AccountKeyring::Alice.to_account_id(), ENDOWMENT),
+				(AccountKeyring::Bob.to_account_id(), ENDOWMENT),
+			],
+		},
+		transaction_payment: Default::default(),
+		council: Default::default(),
+		technical_committee: Default::default(),
+		technical_membership: Default::default(),
+		council_membership: Default::default(),
+		democracy: Default::default(),
+		treasury: Default::default(),
+		template_module: Default::default(),
+		kitties: Default::default(),
+		assets: Default::default(),
+		vesting: Default::default(),
+		claims: Default::default(),
+	}
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+#[test]
+fn the_permissioned_runtime_has_its_own_spec_name() {
+	assert_eq!(&*VERSION.spec_name, "node-template-permissioned");
+
+	type Info = <Runtime as frame_system::Config>::PalletInfo;
+	assert_eq!(Info::index::<ValidatorSet>(), Some(28));
+}
+
+#[test]
+fn added_validators_validate_from_the_session_after_the_next() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) =
+			(AccountKeyring::Alice.to_account_id(), AccountKeyring::Bob.to_account_id());
+		let add_bob = || ValidatorSet::add_validator(Origin::root(), MultiAddress::Id(bob.clone()));
+		assert_noop!(add_bob(), pallet_validator_set::Error::<Runtime>::NoSessionKeys);
+
+		assert_ok!(Session::set_keys(
+			Origin::signed(bob.clone()),
+			session_keys(AccountKeyring::Bob, Ed25519Keyring::Bob),
+			vec![],
+		));
+		assert_ok!(add_bob());
+
+		Session::rotate_session();
+		assert_eq!(Session::validators(), vec![alice.clone()]);
+
+		Session::rotate_session();
+		assert_eq!(Session::validators(), vec![alice, bob]);
+		assert!(Aura::authorities().contains(&AccountKeyring::Bob.public().into()));
+	});
+}
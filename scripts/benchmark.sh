@@ -0,0 +1,59 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems, on the reference hardware that the
+# weights are for.
+#
+# Generates the weights.rs of the given pallets, or of all the pallets of this workspace, from
+# their benchmarks:
+#
+#   ./scripts/benchmark.sh [pallet_template pallet_poe ...]
+#
+# STEPS and REPEAT override the number of steps and repetitions of each benchmark, and
+# NO_OUTPUT=1 only runs the benchmarks, without writing the weights.
+#
+# pallet_validator_set is only in the runtime of the `permissioned` feature, so the node is built
+# with it when pallet_validator_set is among the pallets.
+set -e
+
+PALLETS=(
+  pallet_template
+  pallet_poe
+  pallet_kitties
+  pallet_quadratic_voting
+  pallet_claims
+  pallet_validator_set
+)
+
+if [ $# -gt 0 ] ; then
+  PALLETS=("$@")
+fi
+
+FEATURES=runtime-benchmarks
+if [[ " ${PALLETS[*]} " == *" pallet_validator_set "* ]] ; then
+  FEATURES=runtime-benchmarks,permissioned
+fi
+
+echo "*** Building the node with $FEATURES"
+
+cargo build --release --features "$FEATURES"
+
+for PALLET in "${PALLETS[@]}" ; do
+  # pallet_template's weights are in pallets/template/src/weights.rs, and so on.
+  DIR=pallets/$(echo "${PALLET#pallet_}" | tr _ -)
+  OUTPUT=()
+  if [ -z "$NO_OUTPUT" ] ; then
+    OUTPUT=(--output="$DIR/src/weights.rs" --template=./.maintain/frame-weight-template.hbs)
+  fi
+
+  echo "*** Benchmarking $PALLET"
+
+  ./target/release/node-template benchmark pallet \
+    --chain=dev \
+    --steps="${STEPS:-50}" \
+    --repeat="${REPEAT:-20}" \
+    --pallet="$PALLET" \
+    --extrinsic='*' \
+    --execution=wasm \
+    --wasm-execution=compiled \
+    --heap-pages=4096 \
+    "${OUTPUT[@]}"
+done